@@ -7,6 +7,11 @@ use proc_macro::TokenStream;
 mod measurable;
 mod util;
 
+/// Derives `Measurable`, `PointMeasurable` and `FiniteMeasurable` for an enum
+/// whose variants have no fields, with `BitSubset` as its subset type.
+///
+/// With the `alloc` feature (enabled by that of `mes`), also derives
+/// `OwnedMeasurable`, owning subsets as they are.
 #[proc_macro_derive(Measurable)]
 pub fn derive_measurable(input: TokenStream) -> TokenStream {
     measurable::derive(input)
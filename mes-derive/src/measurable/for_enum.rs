@@ -1,31 +1,28 @@
-use darling::{ast::Fields, FromField, FromVariant};
+use darling::FromVariant;
 use itertools::Itertools;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, Index, Type};
+use syn::Ident;
 
 use crate::util::as_arguments;
 
 use super::Input;
 
 #[derive(Clone, FromVariant)]
-#[darling(supports(newtype, unit))]
+#[darling(supports(unit))]
 pub struct Variant {
-    fields: Fields<VariantField>,
-}
-
-#[derive(Clone, FromField)]
-pub struct VariantField {
-    ty: Type,
+    ident: Ident,
 }
 
 pub(super) fn derive(input: Input) -> TokenStream {
-    let r_ident = input.r_ident();
     let measurable = quote!(::mes::Measurable);
-    let real = quote!(::mes::real::Real);
-    let sized = quote!(::core::marker::Sized);
+    let point_measurable = quote!(::mes::PointMeasurable);
+    let finite_measurable = quote!(::mes::FiniteMeasurable);
+    let bit_subset = quote!(::mes::BitSubset);
+    let subset_proxy = quote!(::mes::SubsetProxy);
+    let local_iterator = quote!(::mes::util::iter::LocalIterator);
     let option = quote!(::core::option::Option);
-    let fn_once = quote!(::core::ops::FnOnce);
+    let with = quote!(::mes::with_locals::with);
 
     let Input {
         ident,
@@ -35,110 +32,152 @@ pub(super) fn derive(input: Input) -> TokenStream {
     let where_clause = generics.where_clause.as_ref();
     let generic_args = as_arguments(&generics);
 
-    eprintln!("{}", generic_args);
+    let variants = data
+        .take_enum()
+        .unwrap()
+        .into_iter()
+        .map(|v| v.ident)
+        .collect_vec();
+    let len = variants.len();
+    let words = len.div_ceil(64);
+    let indices = 0..len;
+    let indices_2 = indices.clone();
+    let variants_2 = variants.clone();
+
+    let owned = if cfg!(feature = "alloc") {
+        let owned_measurable = quote!(::mes::OwnedMeasurable);
+        quote! {
+            #[automatically_derived]
+            impl #generics #owned_measurable for #ident #generic_args #where_clause {
+                type Owned = #bit_subset<#words>;
 
-    let data = data.take_enum().unwrap();
-    let len = data.len();
+                fn subset_to_owned(s: &Self::Subset<'_>) -> ::core::option::Option<Self::Owned> {
+                    ::core::option::Option::Some(*s)
+                }
 
-    let body = if len == 0 {
-        empty(r_ident)
-    } else {
-        let len_m1 = len - 1;
-
-        let types = data
-            .into_iter()
-            .map(|v| {
-                if let Some(VariantField { ty }) = v.fields.into_iter().next() {
-                    quote!(#ty)
-                } else {
-                    quote!(())
+                #[#with]
+                fn subset_from_owned(s: &Self::Owned) -> &'ref Self::Subset<'ref> {
+                    s
                 }
-            })
-            .collect_vec();
+            }
+        }
+    } else {
+        quote!()
+    };
 
-        let indices = (0..len).into_iter().map(Index::from).collect_vec();
+    quote! {
+        #[automatically_derived]
+        impl #generics #measurable for #ident #generic_args #where_clause {
+            type Subset<'a> = #bit_subset<#words> where Self: 'a;
 
-        quote! {
-            type Measure<#r_ident: #real> = (#(<#types as Measurable>::Measure<#r_ident>,)*);
+            fn subset_upcast<'a, 'b: 'a>(s: &'a Self::Subset<'b>) -> &'a Self::Subset<'a> {
+                s
+            }
+
+            #[#with]
+            fn empty_subset() -> &'ref Self::Subset<'ref> {
+                &#bit_subset::empty(#len)
+            }
+
+            #[#with]
+            fn full_subset() -> &'ref Self::Subset<'ref> {
+                &#bit_subset::full(#len)
+            }
+
+            fn subset_is_empty(s: &Self::Subset<'_>) -> bool {
+                s.is_empty()
+            }
+
+            fn subset_is_full(s: &Self::Subset<'_>) -> bool {
+                s.is_full()
+            }
+
+            fn subset_includes(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+                s.includes(t)
+            }
+
+            fn subset_eq(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+                s == t
+            }
 
-            type PMeasure<#r_ident: #real> = ([#r_ident; #len_m1], #(#option<<#types as Measurable>::PMeasure<#r_ident>>),*);
+            #[#with]
+            fn subset_complement(s: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
+                &!*s
+            }
 
-            fn zero<#r_ident: #real>() -> Self::Measure<#r_ident>
+            #[#with]
+            fn subset_union<'a>(
+                mut subsets: impl #local_iterator<Item = #subset_proxy<'a, Self>> + Clone + 'a,
+            ) -> &'ref Self::Subset<'ref>
             where
-                Self::Measure<#r_ident>: #sized,
+                Self: 'a,
             {
-                (#(<#types as Measurable>::zero(),)*)
-            }
+                let mut result = #bit_subset::empty(#len);
+                while let #option::Some(proxy) = subsets.next() {
+                    proxy.with_access(|s| result |= *s);
+                }
 
-            fn total<#r_ident: #real>(m: &Self::Measure<#r_ident>) -> #r_ident {
-                #(<#types as Measurable>::total(&m.#indices))+*
+                &result
             }
 
-            fn normalize<#r_ident: #real>(m: &Self::Measure<#r_ident>) -> #option<Self::PMeasure<#r_ident>>
+            #[#with]
+            fn subset_intersect<'a>(
+                mut subsets: impl #local_iterator<Item = #subset_proxy<'a, Self>> + Clone + 'a,
+            ) -> &'ref Self::Subset<'ref>
             where
-                Self::PMeasure<#r_ident>: #sized,
+                Self: 'a,
             {
-                let mut probabilities: [#r_ident; #len] = [#(<#types as Measurable>::total(&m.#indices)),*];
-                <#r_ident as #real>::normalize(&mut probabilities)?;
-                #option::Some((probabilities[..#len_m1].try_into().ok()?, #(<#types as Measurable>::normalize(&m.#indices)),*))
+                let mut result = #bit_subset::full(#len);
+                while let #option::Some(proxy) = subsets.next() {
+                    proxy.with_access(|s| result &= *s);
+                }
+
+                &result
+            }
+
+            #[#with]
+            fn subset_difference(
+                s: &Self::Subset<'_>,
+                t: &Self::Subset<'_>,
+            ) -> &'ref Self::Subset<'ref> {
+                &(*s - *t)
             }
 
-            fn with_normalized<#r_ident: #real, T>(
-                m: &Self::Measure<#r_ident>,
-                f: impl for<'a> #fn_once(&'a Self::PMeasure<#r_ident>) -> T,
-            ) -> Option<T> {
-                #option::Some(f(&<Self as Measurable>::normalize(m)?))
+            #[#with]
+            fn subset_symmetric_difference(
+                s: &Self::Subset<'_>,
+                t: &Self::Subset<'_>,
+            ) -> &'ref Self::Subset<'ref> {
+                &(*s ^ *t)
             }
         }
-    };
 
-    quote! {
         #[automatically_derived]
-        impl #generics #measurable for #ident #generic_args #where_clause {
-            #body
+        impl #generics #point_measurable for #ident #generic_args #where_clause {
+            #[#with]
+            fn point_subset(&self) -> &'ref Self::Subset<'ref> {
+                &#bit_subset::singleton(#len, #finite_measurable::index(self))
+            }
         }
-    }
-}
 
-fn empty(r_ident: Ident) -> TokenStream {
-    let zero = quote!(::mes::num_traits::Zero);
-    let real = quote!(::mes::real::Real);
-    let sized = quote!(::core::marker::Sized);
-    let option = quote!(::core::option::Option);
-    let fn_once = quote!(::core::ops::FnOnce);
-
-    quote! {
-        type Measure<#r_ident: #real> = ();
-
-        type PMeasure<#r_ident: #real> = ::mes::void::Void;
-
-        #[inline]
-        fn zero<#r_ident: #real>() -> Self::Measure<#r_ident>
-        where
-            Self::Measure<#r_ident>: #sized,
-        {
-            ()
-        }
+        #[automatically_derived]
+        impl #generics #finite_measurable for #ident #generic_args #where_clause {
+            const CARDINALITY: usize = #len;
 
-        #[inline]
-        fn total<#r_ident: #real>(_m: &Self::Measure<#r_ident>) -> #r_ident {
-            <#r_ident as #zero>::zero()
-        }
+            fn index(&self) -> usize {
+                match *self {
+                    #(Self::#variants => #indices,)*
+                }
+            }
 
-        #[inline]
-        fn normalize<#r_ident: #real>(_m: &Self::Measure<#r_ident>) -> #option<Self::PMeasure<#r_ident>>
-        where
-            Self::PMeasure<#r_ident>: #sized,
-        {
-            #option::None
+            fn from_index(index: usize) -> #option<Self> {
+                match index {
+                    #(#indices_2 => #option::Some(Self::#variants_2),)*
+                    _ => #option::None,
+                }
+            }
         }
 
-        #[inline]
-        fn with_normalized<#r_ident: #real, T>(
-            _m: &Self::Measure<#r_ident>,
-            _f: impl for<'a> #fn_once(&'a Self::PMeasure<#r_ident>) -> T,
-        ) -> Option<T> {
-            #option::None
-        }
+        #owned
     }
 }
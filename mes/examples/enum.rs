@@ -1,10 +1,20 @@
-// use mes::Measurable;
+use mes::{FiniteMeasurable, Measurable, PointMeasurable};
 
-// #[derive(Debug, Measurable)]
+#[derive(Debug, Clone, Copy, PartialEq, Measurable)]
 enum MyEnum {
-    // A(f32),
-    B(bool),
+    A,
+    B,
     C,
 }
 
-pub fn main() {}
+pub fn main() {
+    for x in MyEnum::points() {
+        let included = MyEnum::points()
+            .filter(|y| {
+                x.with_point_subset(|s| y.with_point_subset(|t| MyEnum::subset_includes(s, t)))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(included, [x]);
+        println!("{:?} has index {}", x, x.index());
+    }
+}
@@ -5,11 +5,14 @@ use derive_more::{Add, AddAssign, Mul, MulAssign};
 use with_locals::with;
 
 use crate::{
-    real::Real,
-    util::{iter::LocalIterator, proxy::Proxy},
-    DiracMeasure, Measurable, MeasurableFn, Measure, PointMeasurable, PointMeasure, SubsetProxy,
+    real::Real, util::iter::LocalIterator, BitSubset, ConditionableMeasure, DiracMeasure,
+    FiniteMeasurable, FiniteMeasure, IntegrableMeasure, Measurable, MeasurableFn, Measure,
+    PointMeasurable, PointMeasure, SubsetProxy,
 };
 
+#[cfg(feature = "alloc")]
+use crate::OwnedMeasurable;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// A subset of [`bool`].
 pub struct BoolSubset {
@@ -26,6 +29,20 @@ pub struct BoolFunction<'a, T: Measurable + ?Sized + 'a> {
     pub true_primage: T::Subset<'a>,
 }
 
+impl From<BoolSubset> for BitSubset<1> {
+    fn from(s: BoolSubset) -> Self {
+        let mut result = Self::empty(bool::CARDINALITY);
+        if s.includes_false {
+            result.insert(false.index());
+        }
+        if s.includes_true {
+            result.insert(true.index());
+        }
+
+        result
+    }
+}
+
 impl Not for BoolSubset {
     type Output = Self;
 
@@ -95,6 +112,18 @@ impl Measurable for bool {
         !s.includes_true && !s.includes_false
     }
 
+    fn subset_is_full(s: &Self::Subset<'_>) -> bool {
+        s.includes_true && s.includes_false
+    }
+
+    fn subset_includes(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+        (s.includes_true || !t.includes_true) && (s.includes_false || !t.includes_false)
+    }
+
+    fn subset_eq(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+        s == t
+    }
+
     #[with]
     fn subset_complement(s: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
         &!*s
@@ -126,6 +155,52 @@ impl Measurable for bool {
 
         &result
     }
+
+    #[with]
+    fn subset_intersect<'a>(
+        mut subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
+    ) -> &'ref Self::Subset<'ref>
+    where
+        Self: 'a,
+    {
+        let mut result = BoolSubset {
+            includes_true: true,
+            includes_false: true,
+        };
+
+        while let Some(proxy) = subsets.next() {
+            {
+                let s: &'ref _ = proxy.access();
+                result.includes_true &= s.includes_true;
+                result.includes_false &= s.includes_false;
+            }
+
+            if !result.includes_true && !result.includes_false {
+                break;
+            }
+        }
+
+        &result
+    }
+
+    #[with]
+    fn subset_difference(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
+        &BoolSubset {
+            includes_true: s.includes_true && !t.includes_true,
+            includes_false: s.includes_false && !t.includes_false,
+        }
+    }
+
+    #[with]
+    fn subset_symmetric_difference(
+        s: &Self::Subset<'_>,
+        t: &Self::Subset<'_>,
+    ) -> &'ref Self::Subset<'ref> {
+        &BoolSubset {
+            includes_true: s.includes_true != t.includes_true,
+            includes_false: s.includes_false != t.includes_false,
+        }
+    }
 }
 
 impl PointMeasurable for bool {
@@ -138,6 +213,37 @@ impl PointMeasurable for bool {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl OwnedMeasurable for bool {
+    type Owned = BoolSubset;
+
+    fn subset_to_owned(s: &Self::Subset<'_>) -> Option<Self::Owned> {
+        Some(*s)
+    }
+
+    #[with]
+    fn subset_from_owned(s: &Self::Owned) -> &'ref Self::Subset<'ref> {
+        s
+    }
+}
+
+impl FiniteMeasurable for bool {
+    const CARDINALITY: usize = 2;
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Add, AddAssign, Mul, MulAssign)]
 /// A measure on [`bool`].
 pub struct BoolMeasure<R: Real> {
@@ -226,3 +332,30 @@ impl<'subset, R: Real> DiracMeasure<'subset> for BoolMeasure<R> {
         }
     }
 }
+
+impl<'subset, R: Real> FiniteMeasure<'subset> for BoolMeasure<R> {
+    fn from_point_masses(mut mass: impl FnMut(&Self::Space) -> Self::R) -> Self {
+        Self {
+            true_value: mass(&true),
+            false_value: mass(&false),
+        }
+    }
+}
+
+impl<'subset, R: Real> ConditionableMeasure<'subset> for BoolMeasure<R> {
+    fn condition(&self, event: &BoolSubset) -> Option<Self::PMeasure> {
+        let restrict = |value, included| if included { value } else { R::zero() };
+        Some(BoolPMeasure {
+            true_value: R::normalize_static([
+                restrict(self.true_value, event.includes_true),
+                restrict(self.false_value, event.includes_false),
+            ])?[0],
+        })
+    }
+}
+
+impl<'subset, R: Real> IntegrableMeasure<'subset> for BoolMeasure<R> {
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        self.true_value * f(&true) + self.false_value * f(&false)
+    }
+}
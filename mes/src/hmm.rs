@@ -0,0 +1,349 @@
+//! Hidden Markov models over finite measurable spaces.
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+use crate::{
+    markov::MarkovChain, real::Real, Categorical, FiniteMeasurable, FiniteMeasure, Kernel, Measure,
+};
+
+/// A hidden Markov model, in which a hidden state evolves according to a
+/// [`MarkovChain`] and each state emits an observation.
+///
+/// Both the hidden states and the observations may be any
+/// [`FiniteMeasurable`] types:
+/// ```
+/// use mes::{hmm::HiddenMarkovModel, Measurable};
+///
+/// #[derive(Debug, PartialEq, Measurable)]
+/// enum Weather {
+///     Sunny,
+///     Rainy,
+/// }
+///
+/// // Whether the umbrella was seen, given the weather.
+/// let model = HiddenMarkovModel::from_fns(
+///     |_: &Weather| 0.5f64,
+///     |from: &Weather, to: &Weather| if from == to { 0.7 } else { 0.3 },
+///     |weather: &Weather, umbrella: &bool| match (weather, umbrella) {
+///         (Weather::Rainy, true) => 0.9,
+///         (Weather::Rainy, false) => 0.1,
+///         (Weather::Sunny, true) => 0.2,
+///         (Weather::Sunny, false) => 0.8,
+///     },
+/// );
+///
+/// let filtered = model.filter(&[true, true]).unwrap();
+/// assert!((filtered[1].mass(&Weather::Rainy) - 0.883).abs() < 1e-3);
+///
+/// let path = model.viterbi(&[true, true, false]).unwrap();
+/// assert_eq!(path, [Weather::Rainy, Weather::Rainy, Weather::Sunny]);
+/// ```
+pub struct HiddenMarkovModel<S, O, R> {
+    initial: Categorical<S, R>,
+    transitions: MarkovChain<S, R>,
+
+    /// The emission probabilities, with the row of each state stored
+    /// contiguously.
+    emissions: Vec<R>,
+    phantom: PhantomData<fn(&O) -> O>,
+}
+
+impl<S: FiniteMeasurable, O: FiniteMeasurable, R: Real> HiddenMarkovModel<S, O, R> {
+    /// Constructs a model from the distribution of the initial state, the
+    /// transitions of the hidden state, and a kernel giving the distribution of
+    /// the observation emitted by each state.
+    pub fn new<'subset, M: Measure<'subset, Space = S, R = R>, E: Kernel<'subset, Domain = S>>(
+        initial: &M,
+        transitions: MarkovChain<S, R>,
+        emissions: E,
+    ) -> Self
+    where
+        M::Measurement: Copy + Into<R>,
+        E::Measure: Measure<'subset, Space = O, R = R>,
+        <E::Measure as Measure<'subset>>::Measurement: Copy + Into<R>,
+    {
+        let initial = Categorical::from_point_masses(|x: &S| {
+            x.with_point_subset(|p| initial.with_measure(S::subset_upcast(p), |m| (*m).into()))
+        });
+        let mut probabilities = Vec::with_capacity(S::CARDINALITY * O::CARDINALITY);
+        for state in S::points() {
+            let m = emissions.apply(&state);
+            probabilities.extend(O::points().map(|y| {
+                y.with_point_subset(|p| m.with_measure(O::subset_upcast(p), |x| (*x).into()))
+            }));
+        }
+
+        Self {
+            initial,
+            transitions,
+            emissions: probabilities,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Constructs a model from the probability of each initial state, the
+    /// probability of each transition, and the probability of each observation
+    /// being emitted by each state.
+    pub fn from_fns(
+        initial: impl FnMut(&S) -> R,
+        transition: impl FnMut(&S, &S) -> R,
+        mut emission: impl FnMut(&S, &O) -> R,
+    ) -> Self {
+        let mut emissions = Vec::with_capacity(S::CARDINALITY * O::CARDINALITY);
+        for state in S::points() {
+            emissions.extend(O::points().map(|y| emission(&state, &y)));
+        }
+
+        Self {
+            initial: Categorical::from_point_masses(initial),
+            transitions: MarkovChain::from_fn(transition),
+            emissions,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The distribution of the initial hidden state.
+    pub fn initial(&self) -> &Categorical<S, R> {
+        &self.initial
+    }
+
+    /// The transitions of the hidden state.
+    pub fn transitions(&self) -> &MarkovChain<S, R> {
+        &self.transitions
+    }
+
+    /// The probability that the given state emits the given observation.
+    pub fn emission(&self, state: &S, observation: &O) -> R {
+        self.emission_at(state.index(), observation)
+    }
+
+    fn emission_at(&self, state: usize, observation: &O) -> R {
+        self.emissions[state * O::CARDINALITY + observation.index()]
+    }
+
+    /// Runs the forward algorithm, giving the filtered distributions along
+    /// with the probability of each observation given the previous ones.
+    ///
+    /// Returns [`None`] if the observations are impossible.
+    fn forward(&self, observations: &[O]) -> Option<(Vec<Vec<R>>, Vec<R>)> {
+        let mut alphas: Vec<Vec<R>> = Vec::with_capacity(observations.len());
+        let mut scales = Vec::with_capacity(observations.len());
+        for y in observations {
+            let mut alpha: Vec<R> = match alphas.last() {
+                None => self.initial.masses().to_vec(),
+                Some(previous) => {
+                    let mut alpha = vec![R::zero(); S::CARDINALITY];
+                    for (i, a) in previous.iter().enumerate() {
+                        for (next, p) in alpha.iter_mut().zip(self.transitions.row(i)) {
+                            *next += *a * *p;
+                        }
+                    }
+
+                    alpha
+                }
+            };
+            for (i, a) in alpha.iter_mut().enumerate() {
+                *a *= self.emission_at(i, y);
+            }
+
+            let scale = alpha.iter().fold(R::zero(), |sum, a| sum + *a);
+            let factor = scale.recip();
+            if !factor.is_finite() {
+                return None;
+            }
+            alpha.iter_mut().for_each(|a| *a *= factor);
+            alphas.push(alpha);
+            scales.push(scale);
+        }
+
+        Some((alphas, scales))
+    }
+
+    /// Computes the filtered distributions of the hidden state, i.e., the
+    /// distribution of each state given the observations up to and including
+    /// it.
+    ///
+    /// Returns [`None`] if the observations are impossible.
+    pub fn filter(&self, observations: &[O]) -> Option<Vec<Categorical<S, R>>> {
+        let (alphas, _) = self.forward(observations)?;
+        Some(alphas.into_iter().map(to_categorical).collect())
+    }
+
+    /// Computes the smoothed distributions of the hidden state, i.e., the
+    /// distribution of each state given all of the observations, using the
+    /// forward-backward algorithm.
+    ///
+    /// Returns [`None`] if the observations are impossible.
+    ///
+    /// ```
+    /// use mes::hmm::HiddenMarkovModel;
+    ///
+    /// // A state which never changes, observed through a noisy channel.
+    /// let model = HiddenMarkovModel::from_fns(
+    ///     |_: &bool| 0.5f64,
+    ///     |from: &bool, to: &bool| if from == to { 1.0 } else { 0.0 },
+    ///     |state: &bool, y: &bool| if state == y { 0.75 } else { 0.25 },
+    /// );
+    ///
+    /// // Later observations inform earlier states.
+    /// let filtered = model.filter(&[true, true]).unwrap();
+    /// let smoothed = model.smooth(&[true, true]).unwrap();
+    /// assert_eq!(filtered[0].mass(&true), 0.75);
+    /// assert!((smoothed[0].mass(&true) - 0.9).abs() < 1e-12);
+    /// ```
+    pub fn smooth(&self, observations: &[O]) -> Option<Vec<Categorical<S, R>>> {
+        let (alphas, scales) = self.forward(observations)?;
+
+        let mut result = Vec::with_capacity(observations.len());
+        let mut beta = vec![R::one(); S::CARDINALITY];
+        for t in (0..observations.len()).rev() {
+            let mut gamma: Vec<R> = alphas[t].iter().zip(&beta).map(|(a, b)| *a * *b).collect();
+            R::normalize(&mut gamma)?;
+            result.push(to_categorical(gamma));
+
+            if t > 0 {
+                let y = &observations[t];
+                let weighted: Vec<R> = beta
+                    .iter()
+                    .enumerate()
+                    .map(|(j, b)| self.emission_at(j, y) * *b)
+                    .collect();
+                let factor = scales[t].recip();
+                beta = (0..S::CARDINALITY)
+                    .map(|i| {
+                        let row = self.transitions.row(i);
+                        row.iter()
+                            .zip(&weighted)
+                            .fold(R::zero(), |sum, (p, w)| sum + *p * *w)
+                            * factor
+                    })
+                    .collect();
+            }
+        }
+
+        result.reverse();
+        Some(result)
+    }
+
+    /// Computes the most likely sequence of hidden states given the
+    /// observations, using the Viterbi algorithm.
+    ///
+    /// Returns [`None`] if the observations are impossible.
+    pub fn viterbi(&self, observations: &[O]) -> Option<Vec<S>> {
+        let mut deltas: Vec<R> = Vec::new();
+        let mut back_pointers: Vec<Vec<usize>> = Vec::with_capacity(observations.len());
+        for (t, y) in observations.iter().enumerate() {
+            let mut next = Vec::with_capacity(S::CARDINALITY);
+            if t == 0 {
+                next.extend(self.initial.masses().iter().copied());
+            } else {
+                let mut pointers = Vec::with_capacity(S::CARDINALITY);
+                for j in 0..S::CARDINALITY {
+                    let (best, value) = argmax(
+                        deltas
+                            .iter()
+                            .enumerate()
+                            .map(|(i, d)| *d * self.transitions.row(i)[j]),
+                    );
+                    next.push(value);
+                    pointers.push(best);
+                }
+                back_pointers.push(pointers);
+            }
+            for (i, d) in next.iter_mut().enumerate() {
+                *d *= self.emission_at(i, y);
+            }
+
+            // Rescale so that the largest value is one, to avoid underflow.
+            let (_, max) = argmax(next.iter().copied());
+            let factor = max.recip();
+            if !factor.is_finite() {
+                return None;
+            }
+            next.iter_mut().for_each(|d| *d *= factor);
+            deltas = next;
+        }
+
+        if observations.is_empty() {
+            return Some(Vec::new());
+        }
+
+        let (mut state, _) = argmax(deltas.into_iter());
+        let mut path = vec![state];
+        for pointers in back_pointers.iter().rev() {
+            state = pointers[state];
+            path.push(state);
+        }
+
+        path.reverse();
+        Some(path.into_iter().filter_map(S::from_index).collect())
+    }
+
+    /// Computes the probability of the observations.
+    ///
+    /// For long sequences of observations this may underflow, in which case
+    /// `log_likelihood` should be used instead.
+    pub fn likelihood(&self, observations: &[O]) -> R {
+        self.forward(observations)
+            .map_or_else(R::zero, |(_, scales)| {
+                scales.into_iter().fold(R::one(), |product, s| product * s)
+            })
+    }
+
+    #[cfg(any(feature = "libm", feature = "std"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+    /// Computes the natural logarithm of the probability of the observations.
+    ///
+    /// Returns negative infinity if the observations are impossible.
+    pub fn log_likelihood(&self, observations: &[O]) -> R
+    where
+        R: num_traits::Float,
+    {
+        self.forward(observations)
+            .map_or_else(<R as num_traits::Float>::neg_infinity, |(_, scales)| {
+                scales.into_iter().fold(R::zero(), |sum, s| sum + s.ln())
+            })
+    }
+}
+
+/// Finds the index and value of the largest of the given values, preferring the
+/// first in case of ties.
+fn argmax<R: Real>(values: impl Iterator<Item = R>) -> (usize, R) {
+    values.enumerate().fold(
+        (0, R::zero()),
+        |best, (i, x)| if x > best.1 { (i, x) } else { best },
+    )
+}
+
+/// Converts masses of the points, in order of index, to a [`Categorical`].
+fn to_categorical<S: FiniteMeasurable, R: Real>(masses: Vec<R>) -> Categorical<S, R> {
+    Categorical::from_point_masses(|x: &S| masses[x.index()])
+}
+
+impl<S, O, R: Clone> Clone for HiddenMarkovModel<S, O, R> {
+    fn clone(&self) -> Self {
+        Self {
+            initial: self.initial.clone(),
+            transitions: self.transitions.clone(),
+            emissions: self.emissions.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, O, R: Debug> Debug for HiddenMarkovModel<S, O, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HiddenMarkovModel")
+            .field("initial", &self.initial)
+            .field("transitions", &self.transitions)
+            .field("emissions", &self.emissions)
+            .finish()
+    }
+}
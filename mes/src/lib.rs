@@ -12,19 +12,24 @@ mod measurable;
 pub use measurable::*;
 
 pub mod boolean;
+pub mod hmm;
+pub mod markov;
 pub mod pair;
 pub mod real;
+pub mod special;
 pub mod unit;
 pub mod util;
 // pub mod vector;
 
-// #[cfg(feature = "derive")]
-// #[doc(hidden)]
-// // #[cfg_attr(doc_cfg, doc(cfg(feature = "macros")))]
-// pub use mes_derive::Measurable;
+#[cfg(feature = "derive")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "derive")))]
+pub use mes_derive::Measurable;
 
 #[doc(hidden)]
 pub use void;
 
 #[doc(hidden)]
 pub use num_traits;
+
+#[doc(hidden)]
+pub use with_locals;
@@ -0,0 +1,439 @@
+//! Discrete-time Markov chains over finite measurable spaces.
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+use crate::{real::Real, Categorical, FiniteMeasurable, FiniteMeasure, Kernel, Measure};
+
+/// A time-homogeneous Markov chain on a finite space, given by its transition
+/// probabilities.
+///
+/// The chain is a [`Kernel`] mapping each state to the distribution of the
+/// next state, so it can be used anywhere kernels are accepted:
+/// ```
+/// use mes::{boolean::BoolMeasure, markov::MarkovChain, DiracMeasure, Kernel, PointMeasure};
+///
+/// // A state which flips with probability 1/4.
+/// let chain =
+///     MarkovChain::from_fn(|from: &bool, to: &bool| if from == to { 0.75f64 } else { 0.25 });
+/// assert_eq!(chain.apply(&true).with_measure_at(&false, |x| *x), 0.25);
+///
+/// let m: BoolMeasure<f64> = chain.propagate(&BoolMeasure::dirac(&true), 2);
+/// assert_eq!(m.with_measure_at(&true, |x| *x), 0.625);
+/// ```
+pub struct MarkovChain<S, R> {
+    /// The transition probabilities, with the row of each state stored
+    /// contiguously.
+    transitions: Vec<R>,
+    phantom: PhantomData<fn(&S) -> S>,
+}
+
+impl<S: FiniteMeasurable, R: Real> MarkovChain<S, R> {
+    /// Constructs the chain in which the probability of moving from `from` to
+    /// `to` is `probability(from, to)`.
+    pub fn from_fn(mut probability: impl FnMut(&S, &S) -> R) -> Self {
+        let mut transitions = Vec::with_capacity(S::CARDINALITY * S::CARDINALITY);
+        for from in S::points() {
+            transitions.extend(S::points().map(|to| probability(&from, &to)));
+        }
+
+        Self {
+            transitions,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Constructs the chain whose transitions are given by a kernel.
+    pub fn from_kernel<'subset, K: Kernel<'subset, Domain = S>>(kernel: K) -> Self
+    where
+        K::Measure: Measure<'subset, Space = S, R = R>,
+        <K::Measure as Measure<'subset>>::Measurement: Copy + Into<R>,
+    {
+        let mut transitions = Vec::with_capacity(S::CARDINALITY * S::CARDINALITY);
+        for from in S::points() {
+            let m = kernel.apply(&from);
+            transitions.extend(S::points().map(|to| {
+                to.with_point_subset(|p| m.with_measure(S::subset_upcast(p), |x| (*x).into()))
+            }));
+        }
+
+        Self {
+            transitions,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The probability of moving from `from` to `to` in one step.
+    pub fn probability(&self, from: &S, to: &S) -> R {
+        self.row(from.index())[to.index()]
+    }
+
+    /// The transition probabilities out of the state with the given index, in
+    /// order of index.
+    pub(crate) fn row(&self, index: usize) -> &[R] {
+        let n = S::CARDINALITY;
+        &self.transitions[index * n..(index + 1) * n]
+    }
+
+    /// Advances the masses of the points, in order of index, by one step.
+    fn step_masses(&self, masses: &[R]) -> Vec<R> {
+        let mut result = vec![R::zero(); S::CARDINALITY];
+        for (from, mass) in masses.iter().enumerate() {
+            if !mass.is_zero() {
+                for (r, p) in result.iter_mut().zip(self.row(from)) {
+                    *r += *mass * *p;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Computes the distribution of the state after one step, given the
+    /// distribution of the current state.
+    pub fn step<'subset, M: FiniteMeasure<'subset, Space = S, R = R>>(&self, measure: &M) -> M
+    where
+        M::Measurement: Copy + Into<R>,
+    {
+        self.propagate(measure, 1)
+    }
+
+    /// Computes the distribution of the state after `n` steps, given the
+    /// distribution of the current state.
+    pub fn propagate<'subset, M: FiniteMeasure<'subset, Space = S, R = R>>(
+        &self,
+        measure: &M,
+        n: usize,
+    ) -> M
+    where
+        M::Measurement: Copy + Into<R>,
+    {
+        let mut masses: Vec<R> = S::points()
+            .map(|x| {
+                x.with_point_subset(|p| measure.with_measure(S::subset_upcast(p), |m| (*m).into()))
+            })
+            .collect();
+        for _ in 0..n {
+            masses = self.step_masses(&masses);
+        }
+
+        M::from_point_masses(|x| masses[x.index()])
+    }
+
+    /// Computes a stationary distribution of the chain by power iteration,
+    /// starting from the uniform distribution.
+    ///
+    /// The iteration runs on the lazy chain, which stays put with probability
+    /// 1/2 and otherwise follows this one. It has the same stationary
+    /// distributions, but also converges for periodic chains. Iteration stops
+    /// once the total change in mass in a step is at most `tolerance`, and
+    /// [`None`] is returned if that does not happen within `max_iterations`
+    /// steps.
+    ///
+    /// ```
+    /// use mes::{markov::MarkovChain, Categorical, Measurable};
+    ///
+    /// #[derive(Debug, PartialEq, Measurable)]
+    /// enum Light {
+    ///     Red,
+    ///     Green,
+    /// }
+    ///
+    /// // A periodic chain, which alternates deterministically.
+    /// let chain =
+    ///     MarkovChain::from_fn(|from: &Light, to: &Light| if from == to { 0.0f64 } else { 1.0 });
+    /// let m: Categorical<Light, f64> = chain.stationary(1e-12, 1000).unwrap();
+    /// assert_eq!(m.masses(), [0.5, 0.5]);
+    /// ```
+    pub fn stationary<'subset, M: FiniteMeasure<'subset, Space = S, R = R>>(
+        &self,
+        tolerance: R,
+        max_iterations: usize,
+    ) -> Option<M> {
+        let half = (R::one() + R::one()).recip();
+        let mut masses = Categorical::<S, R>::uniform().masses().to_vec();
+        for _ in 0..max_iterations {
+            let next: Vec<R> = self
+                .step_masses(&masses)
+                .into_iter()
+                .zip(&masses)
+                .map(|(stepped, current)| half * (stepped + *current))
+                .collect();
+            let change = next
+                .iter()
+                .zip(&masses)
+                .fold(R::zero(), |sum, (a, b)| sum + (*a - *b).abs());
+            masses = next;
+            if change <= tolerance {
+                return Some(M::from_point_masses(|x| masses[x.index()]));
+            }
+        }
+
+        None
+    }
+
+    /// Computes, for each state, the probability that the chain started there
+    /// ever enters `target`.
+    ///
+    /// The probabilities are returned in order of state index.
+    ///
+    /// ```
+    /// use mes::{markov::MarkovChain, Measurable, PointMeasurable};
+    ///
+    /// #[derive(Debug, PartialEq, Measurable)]
+    /// enum Wealth {
+    ///     Broke,
+    ///     One,
+    ///     Two,
+    ///     Rich,
+    /// }
+    ///
+    /// // A fair gambler's ruin, absorbed at either end.
+    /// let chain = MarkovChain::from_fn(|from: &Wealth, to: &Wealth| {
+    ///     use Wealth::*;
+    ///     match (from, to) {
+    ///         (Broke, Broke) | (Rich, Rich) => 1.0f64,
+    ///         (One, Broke | Two) | (Two, One | Rich) => 0.5,
+    ///         _ => 0.0,
+    ///     }
+    /// });
+    ///
+    /// let p = Wealth::Rich.with_point_subset(|rich| chain.absorption_probabilities(rich));
+    /// assert!((p[1] - 1.0 / 3.0).abs() < 1e-12);
+    /// assert!((p[2] - 2.0 / 3.0).abs() < 1e-12);
+    /// assert_eq!([p[0], p[3]], [0.0, 1.0]);
+    /// ```
+    pub fn absorption_probabilities(&self, target: &S::Subset<'_>) -> Vec<R> {
+        let target = self.target_indices(target);
+        let transient = transient_states(&self.reaching(&target), &target);
+
+        let mut result: Vec<R> = target
+            .iter()
+            .map(|t| if *t { R::one() } else { R::zero() })
+            .collect();
+        let rhs = transient
+            .iter()
+            .map(|i| {
+                self.row(*i)
+                    .iter()
+                    .zip(&target)
+                    .filter(|(_, t)| **t)
+                    .fold(R::zero(), |sum, (p, _)| sum + *p)
+            })
+            .collect();
+        if let Some(solution) = self.solve_transient(&transient, rhs) {
+            for (i, x) in transient.iter().zip(solution) {
+                result[*i] = x;
+            }
+        }
+
+        result
+    }
+
+    /// Computes, for each state, the expected number of steps the chain
+    /// started there takes to enter `target`.
+    ///
+    /// The expected times are returned in order of state index. They are zero
+    /// for states in `target`, and infinite for states from which the chain
+    /// may never enter `target`.
+    ///
+    /// ```
+    /// use mes::{markov::MarkovChain, PointMeasurable};
+    ///
+    /// // Each step succeeds with probability 1/4.
+    /// let chain = MarkovChain::from_fn(|from: &bool, to: &bool| match (from, to) {
+    ///     (false, false) => 0.75f64,
+    ///     (false, true) => 0.25,
+    ///     (true, true) => 1.0,
+    ///     (true, false) => 0.0,
+    /// });
+    ///
+    /// let t = true.with_point_subset(|success| chain.hitting_times(success));
+    /// assert_eq!(t, [4.0, 0.0]);
+    ///
+    /// let t = false.with_point_subset(|failure| chain.hitting_times(failure));
+    /// assert_eq!(t, [0.0, f64::INFINITY]);
+    /// ```
+    pub fn hitting_times(&self, target: &S::Subset<'_>) -> Vec<R> {
+        let target = self.target_indices(target);
+
+        // The chain surely enters `target` exactly from the states from which it
+        // cannot reach any state that cannot reach `target`.
+        let reaching = self.reaching(&target);
+        let mut escaping: Vec<bool> = reaching.iter().map(|r| !r).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..S::CARDINALITY {
+                if !escaping[i]
+                    && !target[i]
+                    && self
+                        .row(i)
+                        .iter()
+                        .zip(&escaping)
+                        .any(|(p, e)| *e && !p.is_zero())
+                {
+                    escaping[i] = true;
+                    changed = true;
+                }
+            }
+        }
+        let surely: Vec<bool> = escaping.iter().map(|e| !e).collect();
+        let transient = transient_states(&surely, &target);
+
+        let mut result: Vec<R> = target
+            .iter()
+            .map(|t| if *t { R::zero() } else { R::infinity() })
+            .collect();
+        let rhs = transient.iter().map(|_| R::one()).collect();
+        if let Some(solution) = self.solve_transient(&transient, rhs) {
+            for (i, x) in transient.iter().zip(solution) {
+                result[*i] = x;
+            }
+        }
+
+        result
+    }
+
+    /// Computes which states are in `target`, in order of index.
+    fn target_indices(&self, target: &S::Subset<'_>) -> Vec<bool> {
+        S::points()
+            .map(|x| x.with_point_subset(|p| S::subset_includes(target, p)))
+            .collect()
+    }
+
+    /// Computes which states can reach `target` with positive probability, in
+    /// order of index.
+    fn reaching(&self, target: &[bool]) -> Vec<bool> {
+        let mut result = target.to_vec();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..S::CARDINALITY {
+                if !result[i]
+                    && self
+                        .row(i)
+                        .iter()
+                        .zip(&result)
+                        .any(|(p, r)| *r && !p.is_zero())
+                {
+                    result[i] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Solves `x = rhs + Q x`, where `Q` is the restriction of the transition
+    /// matrix to the given states.
+    fn solve_transient(&self, states: &[usize], mut rhs: Vec<R>) -> Option<Vec<R>> {
+        let n = states.len();
+        let mut matrix = Vec::with_capacity(n * n);
+        for i in states {
+            let row = self.row(*i);
+            matrix.extend(states.iter().map(|j| {
+                let identity = if i == j { R::one() } else { R::zero() };
+                identity - row[*j]
+            }));
+        }
+
+        solve(&mut matrix, &mut rhs)?;
+        Some(rhs)
+    }
+}
+
+impl<S, R: Clone> Clone for MarkovChain<S, R> {
+    fn clone(&self) -> Self {
+        Self {
+            transitions: self.transitions.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, R: Debug> Debug for MarkovChain<S, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MarkovChain")
+            .field("transitions", &self.transitions)
+            .finish()
+    }
+}
+
+impl<S, R: PartialEq> PartialEq for MarkovChain<S, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.transitions == other.transitions
+    }
+}
+
+impl<'subset, S: FiniteMeasurable, R: Real> Kernel<'subset> for MarkovChain<S, R> {
+    type Domain = S;
+
+    type Measure = Categorical<S, R>;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        Categorical::from_point_masses(|to: &S| self.row(x.index())[to.index()])
+    }
+}
+
+/// Computes the indices of the states which satisfy `include` but are not in
+/// `target`.
+fn transient_states(include: &[bool], target: &[bool]) -> Vec<usize> {
+    (0..include.len())
+        .filter(|i| include[*i] && !target[*i])
+        .collect()
+}
+
+/// Solves the square linear system `matrix * x = rhs` in place by Gaussian
+/// elimination with partial pivoting, leaving the solution in `rhs`.
+///
+/// Returns [`None`] if the matrix is singular.
+fn solve<R: Real>(matrix: &mut [R], rhs: &mut [R]) -> Option<()> {
+    let n = rhs.len();
+    for column in 0..n {
+        let pivot = (column..n).max_by(|a, b| {
+            let a = matrix[a * n + column].abs();
+            let b = matrix[b * n + column].abs();
+            a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+        })?;
+        if matrix[pivot * n + column].is_zero() {
+            return None;
+        }
+        if pivot != column {
+            for k in 0..n {
+                matrix.swap(pivot * n + k, column * n + k);
+            }
+            rhs.swap(pivot, column);
+        }
+
+        let factor = matrix[column * n + column].recip();
+        for row in column + 1..n {
+            let scale = matrix[row * n + column] * factor;
+            if !scale.is_zero() {
+                for k in column..n {
+                    let x = matrix[column * n + k];
+                    matrix[row * n + k] -= scale * x;
+                }
+                let x = rhs[column];
+                rhs[row] -= scale * x;
+            }
+        }
+    }
+
+    for row in (0..n).rev() {
+        let mut x = rhs[row];
+        for k in row + 1..n {
+            x -= matrix[row * n + k] * rhs[k];
+        }
+        rhs[row] = x / matrix[row * n + row];
+    }
+
+    Some(())
+}
@@ -0,0 +1,221 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    ops::{Mul, MulAssign},
+};
+use with_locals::with;
+
+use crate::{
+    real::Real, ConditionableMeasure, DiracMeasure, FiniteMeasurable, FiniteMeasure,
+    IntegrableMeasure, Measurable, Measure, PointMeasure,
+};
+
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// A measure on a finite space, given by the mass it assigns to each point.
+///
+/// This can represent any measure on a [`FiniteMeasurable`] space, including
+/// those derived for enums:
+/// ```
+/// use mes::{Categorical, FiniteMeasure, Measurable, PointMeasure};
+///
+/// #[derive(Debug, PartialEq, Measurable)]
+/// enum Weather {
+///     Sunny,
+///     Cloudy,
+///     Rainy,
+/// }
+///
+/// let m = Categorical::from_point_masses(|w| match w {
+///     Weather::Sunny => 0.5f64,
+///     Weather::Cloudy => 0.25,
+///     Weather::Rainy => 0.25,
+/// });
+/// assert_eq!(m.with_measure_at(&Weather::Cloudy, |x| *x), 0.25);
+/// assert_eq!(m.masses(), [0.5, 0.25, 0.25]);
+/// ```
+pub struct Categorical<T, R> {
+    masses: Vec<R>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: FiniteMeasurable, R: Real> Categorical<T, R> {
+    /// Constructs a measure from the masses of the points, in order of index.
+    ///
+    /// Returns [`None`] if the number of masses is not [`T::CARDINALITY`].
+    ///
+    /// [`T::CARDINALITY`]: FiniteMeasurable::CARDINALITY
+    pub fn from_masses(masses: Vec<R>) -> Option<Self> {
+        (masses.len() == T::CARDINALITY).then_some(Self {
+            masses,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Constructs the zero measure.
+    pub fn zero() -> Self {
+        Self {
+            masses: (0..T::CARDINALITY).map(|_| R::zero()).collect(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Constructs the uniform probability measure.
+    pub fn uniform() -> Self {
+        let mass = R::from(T::CARDINALITY).map_or_else(R::zero, R::recip);
+        Self::from_point_masses(|_| mass)
+    }
+
+    /// The masses of the points, in order of index.
+    pub fn masses(&self) -> &[R] {
+        &self.masses
+    }
+
+    /// The mass of the given point.
+    pub fn mass(&self, point: &T) -> R {
+        self.masses[point.index()]
+    }
+
+    /// The total mass of the measure.
+    pub fn total(&self) -> R {
+        self.masses.iter().fold(R::zero(), |sum, m| sum + *m)
+    }
+}
+
+impl<T, R: Clone> Clone for Categorical<T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            masses: self.masses.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, R: Debug> Debug for Categorical<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Categorical")
+            .field("masses", &self.masses)
+            .finish()
+    }
+}
+
+impl<T, R: PartialEq> PartialEq for Categorical<T, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.masses == other.masses
+    }
+}
+
+impl<T, R: Real> Mul<R> for Categorical<T, R> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<T, R: Real> MulAssign<R> for Categorical<T, R> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.masses.iter_mut().for_each(|m| *m *= rhs);
+    }
+}
+
+impl<'subset, T: FiniteMeasurable, R: Real> Measure<'subset> for Categorical<T, R> {
+    type R = R;
+
+    type Space = T;
+
+    type Measurement = R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &self.integrate(|x| {
+            if x.with_point_subset(|p| T::subset_includes(domain, p)) {
+                R::one()
+            } else {
+                R::zero()
+            }
+        })
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        let mut masses = self.masses.clone();
+        R::normalize(&mut masses)?;
+        Some(Self {
+            masses,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<'subset, T: FiniteMeasurable, R: Real> PointMeasure<'subset> for Categorical<T, R> {
+    type PointMeasurement = R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &self.masses[value.index()]
+    }
+}
+
+impl<'subset, T: FiniteMeasurable, R: Real> DiracMeasure<'subset> for Categorical<T, R> {
+    fn dirac(point: &Self::Space) -> Self {
+        let index = point.index();
+        Self::from_point_masses(|x| {
+            if x.index() == index {
+                R::one()
+            } else {
+                R::zero()
+            }
+        })
+    }
+}
+
+impl<'subset, T: FiniteMeasurable, R: Real> FiniteMeasure<'subset> for Categorical<T, R> {
+    fn from_point_masses(mut mass: impl FnMut(&Self::Space) -> Self::R) -> Self {
+        Self {
+            masses: T::points().map(|x| mass(&x)).collect(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'subset, T: FiniteMeasurable, R: Real> ConditionableMeasure<'subset> for Categorical<T, R> {
+    fn condition(&self, event: &<Self::Space as Measurable>::Subset<'_>) -> Option<Self::PMeasure> {
+        let mut masses: Vec<R> = T::points()
+            .zip(&self.masses)
+            .map(|(x, m)| {
+                if x.with_point_subset(|p| T::subset_includes(event, p)) {
+                    *m
+                } else {
+                    R::zero()
+                }
+            })
+            .collect();
+        R::normalize(&mut masses)?;
+        Some(Self {
+            masses,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<'subset, T: FiniteMeasurable, R: Real> IntegrableMeasure<'subset> for Categorical<T, R> {
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        let mut result = R::zero();
+        for (x, m) in T::points().zip(&self.masses) {
+            if !m.is_zero() {
+                result += *m * f(&x);
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,187 @@
+use core::{
+    borrow::Borrow,
+    ops::{Mul, MulAssign},
+};
+use num_traits::{float::FloatCore, Zero};
+use with_locals::with;
+
+use crate::{
+    measurable::{Measurable, PointMeasurable},
+    subset_proxies,
+    util::proxy::Proxy,
+    IntegrableMeasure, Measure, PointMeasure,
+};
+
+/// A measure which is the restriction of another measure to an event.
+///
+/// Constructed by [`condition`], in which case it is the conditional
+/// probability measure given the event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConditionedMeasure<M, S> {
+    measure: M,
+    event: S,
+}
+
+impl<M, S> ConditionedMeasure<M, S> {
+    /// The event on which the measure is conditioned.
+    pub fn event(&self) -> &S {
+        &self.event
+    }
+}
+
+impl<R, M: Mul<R, Output = M>, S> Mul<R> for ConditionedMeasure<M, S> {
+    type Output = Self;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Self {
+            measure: self.measure * rhs,
+            event: self.event,
+        }
+    }
+}
+
+impl<R, M: MulAssign<R>, S> MulAssign<R> for ConditionedMeasure<M, S> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.measure *= rhs
+    }
+}
+
+impl<
+        'subset,
+        M: Measure<'subset> + Clone,
+        S: Borrow<<M::Space as Measurable>::Subset<'subset>> + Clone,
+    > Measure<'subset> for ConditionedMeasure<M, S>
+where
+    M::Space: 'subset,
+    M::Measurement: Copy + Into<M::R>,
+{
+    type R = M::R;
+
+    type Space = M::Space;
+
+    type Measurement = M::R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        let proxies = [
+            Proxy::new(M::Space::subset_upcast(domain)),
+            Proxy::new(M::Space::subset_upcast(self.event.borrow())),
+        ];
+        let s: &'ref _ = M::Space::subset_intersect(subset_proxies::<M::Space>(&proxies));
+        &self
+            .measure
+            .with_measure(M::Space::subset_upcast(s), |x| (*x).into())
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        condition(self.measure.clone(), self.event.clone())
+    }
+}
+
+impl<
+        'subset,
+        M: PointMeasure<'subset> + Clone,
+        S: Borrow<<M::Space as Measurable>::Subset<'subset>> + Clone,
+    > PointMeasure<'subset> for ConditionedMeasure<M, S>
+where
+    M::Space: PointMeasurable + 'subset,
+    M::Measurement: Copy + Into<M::R>,
+    M::PointMeasurement: Copy + Into<M::R>,
+{
+    type PointMeasurement = M::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        let event = self.event.borrow();
+        &if value.with_point_subset(|p| M::Space::subset_includes(event, p)) {
+            self.measure.with_measure_at(value, |x| (*x).into())
+        } else {
+            M::R::zero()
+        }
+    }
+}
+
+impl<
+        'subset,
+        M: IntegrableMeasure<'subset> + Clone,
+        S: Borrow<<M::Space as Measurable>::Subset<'subset>> + Clone,
+    > IntegrableMeasure<'subset> for ConditionedMeasure<M, S>
+where
+    M::Space: PointMeasurable + 'subset,
+    M::Measurement: Copy + Into<M::R>,
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        let event = self.event.borrow();
+        self.measure.integrate(|x| {
+            if x.with_point_subset(|p| M::Space::subset_includes(event, p)) {
+                f(x)
+            } else {
+                M::R::zero()
+            }
+        })
+    }
+}
+
+/// Conditions a measure on an event, giving the conditional probability measure
+/// `A ↦ μ(A ∩ event) / μ(event)`.
+///
+/// Returns [`None`] if the event has zero measure.
+///
+/// The event may be given by value or by reference:
+/// ```
+/// use mes::{
+///     boolean::{BoolMeasure, BoolSubset},
+///     condition, PointMeasurable, PointMeasure,
+/// };
+///
+/// let m = BoolMeasure {
+///     true_value: 0.25f64,
+///     false_value: 0.75f64,
+/// };
+///
+/// let event = BoolSubset {
+///     includes_true: true,
+///     includes_false: false,
+/// };
+/// let c = condition(m, event).unwrap();
+/// assert_eq!(c.with_measure_at(&true, |x| *x), 1.0);
+/// assert_eq!(c.with_measure_at(&false, |x| *x), 0.0);
+///
+/// assert!(false.with_point_subset(|event| condition(m, event).is_some()));
+/// let never = BoolSubset {
+///     includes_true: false,
+///     includes_false: false,
+/// };
+/// assert!(condition(m, never).is_none());
+/// ```
+pub fn condition<
+    'subset,
+    M: Measure<'subset>,
+    S: Borrow<<M::Space as Measurable>::Subset<'subset>>,
+>(
+    measure: M,
+    event: S,
+) -> Option<ConditionedMeasure<M, S>>
+where
+    M::Space: 'subset,
+    M::Measurement: Copy + Into<M::R>,
+{
+    let mass: M::R = measure.with_measure(M::Space::subset_upcast(event.borrow()), |x| (*x).into());
+    let factor = mass.recip();
+    if !factor.is_finite() {
+        return None;
+    }
+
+    Some(ConditionedMeasure {
+        measure: measure * factor,
+        event,
+    })
+}
@@ -0,0 +1,225 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::ops::{Mul, MulAssign};
+#[cfg(feature = "alloc")]
+use with_locals::with;
+
+use crate::{real::Real, Measurable, MeasurableFn, Measure, PointMeasure};
+#[cfg(feature = "alloc")]
+use crate::{FiniteMeasurable, IntegrableMeasure};
+
+/// An object-safe counterpart of [`Measure`], for measures whose measurements
+/// are real numbers.
+///
+/// This is implemented for every applicable [`Measure`], and allows measures of
+/// different types over the same space to be used interchangeably as trait
+/// objects:
+/// ```
+/// use mes::{real::dirac::Dirac, DynMeasure, Measurable};
+///
+/// let measures: Vec<Box<dyn DynMeasure<f64, f64>>> = vec![
+///     Box::new(Dirac {
+///         point: 1.0,
+///         weight: 0.5,
+///     }),
+///     Box::new(Dirac {
+///         point: 2.0,
+///         weight: 2.0,
+///     }),
+/// ];
+///
+/// let total: f64 = measures
+///     .iter()
+///     .map(|m| f64::with_full_subset(|s| m.measure_dyn(f64::subset_upcast(s))))
+///     .sum();
+/// assert_eq!(total, 2.5);
+/// ```
+///
+/// With the `alloc` feature, boxed [`DynMeasure`]s are themselves [`Measure`]s.
+pub trait DynMeasure<'subset, Space: Measurable + ?Sized, R: Real> {
+    /// Computes the measure of the given subset.
+    fn measure_dyn<'a>(&'a self, domain: &'a Space::Subset<'a>) -> R
+    where
+        'subset: 'a;
+
+    /// Scales the measure by the given factor.
+    fn scale_dyn(&mut self, factor: R);
+
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    /// Attempts to normalize the measure into a probability measure.
+    ///
+    /// Returns [`None`] if that is impossible, such as if the measure has zero
+    /// weight.
+    fn normalize_dyn(&self) -> Option<Box<dyn DynMeasure<'subset, Space, R> + 'subset>>;
+}
+
+impl<'subset, M: Measure<'subset> + 'subset> DynMeasure<'subset, M::Space, M::R> for M
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    fn measure_dyn<'a>(&'a self, domain: &'a <M::Space as Measurable>::Subset<'a>) -> M::R
+    where
+        'subset: 'a,
+    {
+        self.with_measure(domain, |x| (*x).into())
+    }
+
+    fn scale_dyn(&mut self, factor: M::R) {
+        *self *= factor;
+    }
+
+    #[cfg(feature = "alloc")]
+    fn normalize_dyn(&self) -> Option<Box<dyn DynMeasure<'subset, M::Space, M::R> + 'subset>> {
+        Some(Box::new(M::from(self.normalize()?)))
+    }
+}
+
+/// An object-safe counterpart of [`PointMeasure`], for measures whose point
+/// measurements are real numbers.
+pub trait DynPointMeasure<'subset, Space: Measurable + ?Sized, R: Real>:
+    DynMeasure<'subset, Space, R>
+{
+    /// Computes the point measure at a given point.
+    fn measure_at_dyn(&self, value: &Space) -> R;
+}
+
+impl<'subset, M: PointMeasure<'subset> + 'subset> DynPointMeasure<'subset, M::Space, M::R> for M
+where
+    M::Measurement: Copy + Into<M::R>,
+    M::PointMeasurement: Copy + Into<M::R>,
+{
+    fn measure_at_dyn(&self, value: &M::Space) -> M::R {
+        self.with_measure_at(value, |x| (*x).into())
+    }
+}
+
+/// An object-safe counterpart of [`MeasurableFn`].
+///
+/// With the `alloc` feature, boxed [`DynMeasurableFn`]s are themselves
+/// [`MeasurableFn`]s.
+pub trait DynMeasurableFn<'subset, Domain: Measurable + ?Sized, Codomain: Measurable + ?Sized> {
+    /// Computes the preimage of the given subset of the codomain, passing it to
+    /// `f`.
+    fn preimage_dyn<'a, 'f>(
+        &'a self,
+        s: &'a Codomain::Subset<'a>,
+        f: &'f mut (dyn for<'b> FnMut(&'b Domain::Subset<'b>) + 'f),
+    ) where
+        'subset: 'a,
+        Domain: 'f;
+}
+
+impl<'subset, F: MeasurableFn<'subset> + ?Sized> DynMeasurableFn<'subset, F::Domain, F::Codomain>
+    for F
+{
+    fn preimage_dyn<'a, 'f>(
+        &'a self,
+        s: &'a <F::Codomain as Measurable>::Subset<'a>,
+        f: &'f mut (dyn for<'b> FnMut(&'b <F::Domain as Measurable>::Subset<'b>) + 'f),
+    ) where
+        'subset: 'a,
+        F::Domain: 'f,
+    {
+        self.with_preimage(s, |p| f(F::Domain::subset_upcast(p)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'subset, Space: Measurable + ?Sized + 'subset, R: Real> Mul<R>
+    for Box<dyn DynMeasure<'subset, Space, R> + 'subset>
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        (*self).scale_dyn(rhs);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'subset, Space: Measurable + ?Sized + 'subset, R: Real> MulAssign<R>
+    for Box<dyn DynMeasure<'subset, Space, R> + 'subset>
+{
+    fn mul_assign(&mut self, rhs: R) {
+        (**self).scale_dyn(rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'subset, Space: Measurable + ?Sized + 'subset, R: Real> Measure<'subset>
+    for Box<dyn DynMeasure<'subset, Space, R> + 'subset>
+{
+    type R = R;
+
+    type Space = Space;
+
+    type Measurement = R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &(**self).measure_dyn(domain)
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        (**self).normalize_dyn()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// On a finite space, any measure can be integrated exactly by summing over the
+/// points of the space.
+impl<'subset, Space: FiniteMeasurable + 'subset, R: Real> IntegrableMeasure<'subset>
+    for Box<dyn DynMeasure<'subset, Space, R> + 'subset>
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        let mut result = R::zero();
+        for x in Space::points() {
+            let mass = x.with_point_subset(|p| (**self).measure_dyn(Space::subset_upcast(p)));
+            if !mass.is_zero() {
+                result += mass * f(&x);
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'subset, 'f: 'subset, Domain: Measurable + ?Sized, Codomain: Measurable + ?Sized>
+    MeasurableFn<'subset> for Box<dyn DynMeasurableFn<'subset, Domain, Codomain> + 'f>
+{
+    type Domain = Domain;
+
+    type Codomain = Codomain;
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
+    fn preimage<'a>(
+        &'a self,
+        s: &'a <Self::Codomain as Measurable>::Subset<'a>,
+    ) -> &'ref <Self::Domain as Measurable>::Subset<'ref>
+    where
+        'subset: 'a,
+    {
+        let mut ret = Some(ret);
+        let mut result = None;
+        (**self).preimage_dyn(s, &mut |p| {
+            result = Some(ret.take().expect("continuation called multiple times!")(p))
+        });
+
+        result.expect("continuation was never called!")
+    }
+}
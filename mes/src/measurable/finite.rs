@@ -0,0 +1,339 @@
+use core::{
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Range, Sub},
+};
+
+use crate::PointMeasurable;
+
+/// A measurable space with finitely many points.
+///
+/// Each point is assigned a unique index in `0..Self::CARDINALITY`, which
+/// allows generic code to enumerate the whole space. For example, the
+/// expectation of a function under a measure can be computed exactly:
+/// ```
+/// use mes::{boolean::BoolMeasure, FiniteMeasurable, Measurable, Measure, PointMeasurable};
+///
+/// let m = BoolMeasure {
+///     true_value: 0.25f64,
+///     false_value: 0.75f64,
+/// };
+///
+/// let expectation: f64 = bool::points()
+///     .map(|x| {
+///         let weight = x.with_point_subset(|s| m.with_measure(bool::subset_upcast(s), |w| *w));
+///         weight * if x { 4.0 } else { 2.0 }
+///     })
+///     .sum();
+/// assert_eq!(expectation, 2.5);
+/// ```
+///
+/// With the `derive` feature, this (along with
+/// [`Measurable`](crate::Measurable) and [`PointMeasurable`]) can be derived
+/// for enums whose variants have no fields:
+/// ```
+/// use mes::{FiniteMeasurable, Measurable};
+///
+/// #[derive(Debug, PartialEq, Measurable)]
+/// enum Suit {
+///     Clubs,
+///     Diamonds,
+///     Hearts,
+///     Spades,
+/// }
+///
+/// assert_eq!(Suit::CARDINALITY, 4);
+/// assert_eq!(Suit::Hearts.index(), 2);
+/// assert_eq!(Suit::from_index(3), Some(Suit::Spades));
+/// ```
+pub trait FiniteMeasurable: PointMeasurable + Sized {
+    /// The number of points in the space.
+    const CARDINALITY: usize;
+
+    /// Computes the index of the given point, which is less than
+    /// [`Self::CARDINALITY`].
+    fn index(&self) -> usize;
+
+    /// Computes the point with the given index.
+    ///
+    /// Returns [`None`] if `index` is not less than [`Self::CARDINALITY`].
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Iterates over all points in the space, in order of index.
+    ///
+    /// ```
+    /// use mes::FiniteMeasurable;
+    ///
+    /// assert!(bool::points().eq([false, true]));
+    /// assert_eq!(<(bool, bool)>::points().len(), 4);
+    /// ```
+    fn points() -> Points<Self> {
+        Points {
+            indices: 0..Self::CARDINALITY,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over all points of a [`FiniteMeasurable`] space.
+///
+/// Returned by [`FiniteMeasurable::points`].
+pub struct Points<T: FiniteMeasurable> {
+    indices: Range<usize>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: FiniteMeasurable> Clone for Points<T> {
+    fn clone(&self) -> Self {
+        Self {
+            indices: self.indices.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: FiniteMeasurable> Iterator for Points<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        T::from_index(self.indices.next()?)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T: FiniteMeasurable> DoubleEndedIterator for Points<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        T::from_index(self.indices.next_back()?)
+    }
+}
+
+impl<T: FiniteMeasurable> ExactSizeIterator for Points<T> {}
+
+impl<T: FiniteMeasurable> FusedIterator for Points<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A subset of a finite space, represented as a set of point indices.
+///
+/// The set is stored as a bitset of `N` 64-bit words, so it can represent
+/// subsets of spaces with at most `64 * N` points. It also records the
+/// cardinality of the space, so that complements are exact:
+/// ```
+/// use mes::BitSubset;
+///
+/// let mut s = BitSubset::<1>::empty(5);
+/// s.insert(1);
+/// s.insert(3);
+///
+/// let c = !s;
+/// assert!(c.indices().eq([0, 2, 4]));
+/// assert!((s | c).is_full());
+/// assert!((s & c).is_empty());
+/// ```
+pub struct BitSubset<const N: usize> {
+    words: [u64; N],
+    cardinality: usize,
+}
+
+impl<const N: usize> BitSubset<N> {
+    /// Constructs the empty subset of a space with the given cardinality.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cardinality` is greater than `64 * N`.
+    pub fn empty(cardinality: usize) -> Self {
+        assert!(
+            cardinality <= 64 * N,
+            "a space of {cardinality} points does not fit in {N} words"
+        );
+        Self {
+            words: [0; N],
+            cardinality,
+        }
+    }
+
+    /// Constructs the full subset of a space with the given cardinality.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cardinality` is greater than `64 * N`.
+    pub fn full(cardinality: usize) -> Self {
+        !Self::empty(cardinality)
+    }
+
+    /// Constructs the subset of a space with the given cardinality containing
+    /// only the point with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cardinality` is greater than `64 * N`, or if `index` is not
+    /// less than `cardinality`.
+    pub fn singleton(cardinality: usize, index: usize) -> Self {
+        let mut result = Self::empty(cardinality);
+        result.insert(index);
+        result
+    }
+
+    /// Constructs the [`BitSubset`] containing the same points as a subset of
+    /// a [`FiniteMeasurable`] space.
+    ///
+    /// ```
+    /// use mes::{BitSubset, Measurable};
+    ///
+    /// let s = bool::with_full_subset(|s| BitSubset::<1>::from_subset::<bool>(s));
+    /// assert!(s.is_full());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T::CARDINALITY` is greater than `64 * N`.
+    pub fn from_subset<T: FiniteMeasurable>(s: &T::Subset<'_>) -> Self {
+        let mut result = Self::empty(T::CARDINALITY);
+        for (index, point) in T::points().enumerate() {
+            if point.with_point_subset(|p| T::subset_includes(s, p)) {
+                result.insert(index);
+            }
+        }
+
+        result
+    }
+
+    /// The cardinality of the space of which this is a subset.
+    pub fn cardinality(&self) -> usize {
+        self.cardinality
+    }
+
+    /// Checks whether the subset contains the point with the given index.
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.cardinality && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Adds the point with the given index to the subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the cardinality of the space.
+    pub fn insert(&mut self, index: usize) {
+        assert!(index < self.cardinality, "point index out of range");
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    /// Removes the point with the given index from the subset.
+    pub fn remove(&mut self, index: usize) {
+        if index < self.cardinality {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    /// Counts the points in the subset.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Checks whether the subset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Checks whether the subset is the whole space.
+    pub fn is_full(&self) -> bool {
+        self.count() == self.cardinality
+    }
+
+    /// Checks whether this subset includes `other` (i.e., whether `other` is a
+    /// subset of `self`).
+    pub fn includes(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(s, t)| t & !s == 0)
+    }
+
+    /// Iterates over the indices of the points in the subset, in increasing
+    /// order.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.cardinality).filter(|i| self.contains(*i))
+    }
+
+    fn zip_with(mut self, rhs: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        debug_assert_eq!(
+            self.cardinality, rhs.cardinality,
+            "subsets of different spaces"
+        );
+        for (s, t) in self.words.iter_mut().zip(rhs.words) {
+            *s = f(*s, t);
+        }
+
+        self
+    }
+}
+
+impl<const N: usize> Not for BitSubset<N> {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        for (i, word) in self.words.iter_mut().enumerate() {
+            let valid = self.cardinality.saturating_sub(64 * i).min(64);
+            let mask = if valid == 64 {
+                u64::MAX
+            } else {
+                (1 << valid) - 1
+            };
+            *word = !*word & mask;
+        }
+
+        self
+    }
+}
+
+impl<const N: usize> BitAnd for BitSubset<N> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |s, t| s & t)
+    }
+}
+
+impl<const N: usize> BitOr for BitSubset<N> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |s, t| s | t)
+    }
+}
+
+impl<const N: usize> BitXor for BitSubset<N> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |s, t| s ^ t)
+    }
+}
+
+impl<const N: usize> Sub for BitSubset<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |s, t| s & !t)
+    }
+}
+
+impl<const N: usize> BitAndAssign for BitSubset<N> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl<const N: usize> BitOrAssign for BitSubset<N> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl<const N: usize> BitXorAssign for BitSubset<N> {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
@@ -0,0 +1,423 @@
+use core::{
+    marker::PhantomData,
+    ops::{Mul, MulAssign},
+};
+use num_traits::float::FloatCore;
+use with_locals::with;
+
+use crate::{
+    real::Real, IntegrableMeasure, Measurable, MeasurableFn, Measure, PointMeasurable, PointMeasure,
+};
+
+/// A stochastic kernel, which maps each point of a domain to a measure on a
+/// codomain.
+///
+/// Kernels generalize [`MeasurableFn`]s (see [`deterministic`]) to random
+/// transitions, such as "given `x`, `y` is `x + 1` or `x - 1` with equal
+/// probability":
+/// ```
+/// use mes::{
+///     bind, compose_kernels, kernel,
+///     real::{dirac::Dirac, interval::Interval},
+///     Kernel, Measure, Mixture,
+/// };
+///
+/// let step = kernel(|x: &f64| -> Mixture<Dirac<f64>> {
+///     [x + 1.0, x - 1.0]
+///         .into_iter()
+///         .map(|point| Dirac { point, weight: 0.5 })
+///         .collect()
+/// });
+///
+/// // After two steps from 0, the walk is at -2, 0 or 2, with probabilities
+/// // 1/4, 1/2 and 1/4.
+/// let start = Dirac::<f64> {
+///     point: 0.0,
+///     weight: 1.0,
+/// };
+/// let m = bind(bind(start, &step), &step);
+/// assert_eq!(m.with_measure(&Interval::closed(-1.0, 3.0), |x| *x), 0.75);
+/// assert_eq!(m.with_measure(&Interval::open(-2.0, 2.0), |x| *x), 0.5);
+///
+/// // Composing the kernels first gives the same transition.
+/// let two_steps = compose_kernels(&step, &step);
+/// let n = two_steps.apply(&0.0);
+/// assert_eq!(n.with_measure(&Interval::closed(-1.0, 3.0), |x| *x), 0.75);
+/// assert_eq!(n.with_measure(&Interval::point(-2.0), |x| *x), 0.25);
+/// ```
+///
+/// Binding integrates over the measure being bound, which must therefore be an
+/// [`IntegrableMeasure`]. Only discrete measures, such as those on finite
+/// spaces, [`Dirac`](crate::real::dirac::Dirac) measures and mixtures of
+/// them, implement it, so a kernel such as "given `x`, `y` is Gaussian around
+/// `x`" can be bound from a discrete measure on `x` but not from a continuous
+/// one.
+pub trait Kernel<'subset> {
+    /// The domain of the kernel.
+    type Domain: Measurable + ?Sized;
+
+    /// The type of measures on the codomain of the kernel.
+    type Measure: Measure<'subset>;
+
+    /// Computes the measure associated with the given point of the domain.
+    fn apply(&self, x: &Self::Domain) -> Self::Measure;
+}
+
+impl<'subset, K: Kernel<'subset> + ?Sized> Kernel<'subset> for &K {
+    type Domain = K::Domain;
+
+    type Measure = K::Measure;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        (**self).apply(x)
+    }
+}
+
+/// A kernel given by a function from points to measures.
+pub struct FnKernel<T: ?Sized, F> {
+    f: F,
+    phantom: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized, F: Clone> Clone for FnKernel<T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, F: Copy> Copy for FnKernel<T, F> {}
+
+impl<'subset, T: Measurable + ?Sized, M: Measure<'subset>, F: Fn(&T) -> M> Kernel<'subset>
+    for FnKernel<T, F>
+{
+    type Domain = T;
+
+    type Measure = M;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        (self.f)(x)
+    }
+}
+
+/// Constructs a kernel from a function from points to measures.
+pub fn kernel<T: Measurable + ?Sized, M, F: Fn(&T) -> M>(f: F) -> FnKernel<T, F> {
+    FnKernel {
+        f,
+        phantom: PhantomData,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A measure obtained by binding a measure to a kernel, i.e., the measure
+/// `A ↦ ∫ kernel(x)(A) dmeasure(x)`.
+///
+/// This is the pushforward of a measure through a (possibly random)
+/// transition.
+pub struct BoundMeasure<M, K> {
+    measure: M,
+    kernel: K,
+}
+
+impl<R, M: Mul<R, Output = M>, K> Mul<R> for BoundMeasure<M, K> {
+    type Output = Self;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Self {
+            measure: self.measure * rhs,
+            kernel: self.kernel,
+        }
+    }
+}
+
+impl<R, M: MulAssign<R>, K> MulAssign<R> for BoundMeasure<M, K> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.measure *= rhs
+    }
+}
+
+impl<
+        'subset,
+        M: IntegrableMeasure<'subset> + Clone,
+        K: Kernel<'subset, Domain = M::Space> + Clone,
+    > Measure<'subset> for BoundMeasure<M, K>
+where
+    K::Measure: Measure<'subset, R = M::R>,
+    <K::Measure as Measure<'subset>>::Measurement: Copy + Into<M::R>,
+{
+    type R = M::R;
+
+    type Space = <K::Measure as Measure<'subset>>::Space;
+
+    type Measurement = M::R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &self.measure.integrate(|x| {
+            self.kernel
+                .apply(x)
+                .with_measure(Self::Space::subset_upcast(domain), |y| (*y).into())
+        })
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        let total = Self::Space::with_full_subset(|s| {
+            self.with_measure(Self::Space::subset_upcast(s), |x| *x)
+        });
+        let factor = total.recip();
+        if !factor.is_finite() {
+            return None;
+        }
+
+        Some(self.clone() * factor)
+    }
+}
+
+impl<
+        'subset,
+        M: IntegrableMeasure<'subset> + Clone,
+        K: Kernel<'subset, Domain = M::Space> + Clone,
+    > PointMeasure<'subset> for BoundMeasure<M, K>
+where
+    K::Measure: PointMeasure<'subset, R = M::R>,
+    <K::Measure as Measure<'subset>>::Measurement: Copy + Into<M::R>,
+    <K::Measure as PointMeasure<'subset>>::PointMeasurement: Copy + Into<M::R>,
+{
+    type PointMeasurement = M::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &self
+            .measure
+            .integrate(|x| self.kernel.apply(x).with_measure_at(value, |y| (*y).into()))
+    }
+}
+
+impl<
+        'subset,
+        M: IntegrableMeasure<'subset> + Clone,
+        K: Kernel<'subset, Domain = M::Space> + Clone,
+    > IntegrableMeasure<'subset> for BoundMeasure<M, K>
+where
+    K::Measure: IntegrableMeasure<'subset, R = M::R>,
+    <K::Measure as Measure<'subset>>::Measurement: Copy + Into<M::R>,
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        self.measure
+            .integrate(|x| self.kernel.apply(x).integrate(&mut f))
+    }
+}
+
+/// Binds a measure to a kernel, giving the measure of the result of sampling
+/// from the measure and then from the kernel.
+///
+/// The measure must be an [`IntegrableMeasure`], so continuous measures cannot
+/// be bound (see [`Kernel`]).
+pub fn bind<'subset, M: IntegrableMeasure<'subset>, K: Kernel<'subset, Domain = M::Space>>(
+    measure: M,
+    kernel: K,
+) -> BoundMeasure<M, K> {
+    BoundMeasure { measure, kernel }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A kernel which is the composition of two other kernels.
+pub struct ComposedKernel<K, L> {
+    first: K,
+    second: L,
+}
+
+impl<'subset, K: Kernel<'subset>, L: Kernel<'subset> + Clone> Kernel<'subset>
+    for ComposedKernel<K, L>
+where
+    K::Measure: IntegrableMeasure<'subset> + Clone,
+    L: Kernel<'subset, Domain = <K::Measure as Measure<'subset>>::Space>,
+    L::Measure: Measure<'subset, R = <K::Measure as Measure<'subset>>::R>,
+    <L::Measure as Measure<'subset>>::Measurement: Copy + Into<<K::Measure as Measure<'subset>>::R>,
+{
+    type Domain = K::Domain;
+
+    type Measure = BoundMeasure<K::Measure, L>;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        bind(self.first.apply(x), self.second.clone())
+    }
+}
+
+/// Composes two kernels, applying `first` and then binding the result to
+/// `second`.
+pub fn compose_kernels<'subset, K: Kernel<'subset>, L: Kernel<'subset>>(
+    first: K,
+    second: L,
+) -> ComposedKernel<K, L> {
+    ComposedKernel { first, second }
+}
+
+/// A deterministic kernel, which maps each point to the Dirac measure at its
+/// image under a function.
+///
+/// Constructed by [`deterministic`].
+pub struct DeterministicKernel<'a, F: ?Sized, R> {
+    function: &'a F,
+    phantom: PhantomData<R>,
+}
+
+impl<'a, F: ?Sized, R> Clone for DeterministicKernel<'a, F, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, F: ?Sized, R> Copy for DeterministicKernel<'a, F, R> {}
+
+impl<'subset, F: MeasurableFn<'subset> + ?Sized, R: Real> Kernel<'subset>
+    for DeterministicKernel<'subset, F, R>
+where
+    F::Domain: PointMeasurable + Clone,
+{
+    type Domain = F::Domain;
+
+    type Measure = ImageDirac<'subset, F, R>;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        ImageDirac {
+            function: self.function,
+            point: x.clone(),
+            weight: R::one(),
+        }
+    }
+}
+
+/// Lifts a measurable function into a deterministic kernel.
+///
+/// Binding a measure to the resulting kernel gives the pushforward of the
+/// measure through the function:
+/// ```
+/// use mes::{
+///     bind,
+///     boolean::{BoolFunction, BoolMeasure, BoolSubset},
+///     deterministic, Measurable, Measure, PointMeasurable,
+/// };
+///
+/// let m = BoolMeasure {
+///     true_value: 0.25f64,
+///     false_value: 0.75f64,
+/// };
+/// let not = BoolFunction::<bool> {
+///     true_primage: BoolSubset {
+///         includes_true: false,
+///         includes_false: true,
+///     },
+/// };
+///
+/// let pushforward = bind(m, deterministic(&not));
+/// let x = true.with_point_subset(|s| pushforward.with_measure(bool::subset_upcast(s), |x| *x));
+/// assert_eq!(x, 0.75);
+/// ```
+pub fn deterministic<'a, F: MeasurableFn<'a> + ?Sized, R: Real>(
+    function: &'a F,
+) -> DeterministicKernel<'a, F, R> {
+    DeterministicKernel {
+        function,
+        phantom: PhantomData,
+    }
+}
+
+/// The weighted Dirac measure at the image of a point under a measurable
+/// function.
+///
+/// The measure of a subset is computed by checking whether its preimage
+/// contains the point, so the function itself never needs to be evaluated.
+pub struct ImageDirac<'a, F: MeasurableFn<'a> + ?Sized, R>
+where
+    F::Domain: Sized,
+{
+    function: &'a F,
+
+    /// The point whose image the measure is concentrated at.
+    pub point: F::Domain,
+
+    /// The weight of the measure.
+    pub weight: R,
+}
+
+impl<'a, F: MeasurableFn<'a> + ?Sized, R: Clone> Clone for ImageDirac<'a, F, R>
+where
+    F::Domain: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            function: self.function,
+            point: self.point.clone(),
+            weight: self.weight.clone(),
+        }
+    }
+}
+
+impl<'a, F: MeasurableFn<'a> + ?Sized, R: Real> Mul<R> for ImageDirac<'a, F, R>
+where
+    F::Domain: Sized,
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        self.weight *= rhs;
+        self
+    }
+}
+
+impl<'a, F: MeasurableFn<'a> + ?Sized, R: Real> MulAssign<R> for ImageDirac<'a, F, R>
+where
+    F::Domain: Sized,
+{
+    fn mul_assign(&mut self, rhs: R) {
+        self.weight *= rhs;
+    }
+}
+
+impl<'subset, F: MeasurableFn<'subset> + ?Sized, R: Real> Measure<'subset>
+    for ImageDirac<'subset, F, R>
+where
+    F::Domain: PointMeasurable + Clone,
+{
+    type R = R;
+
+    type Space = F::Codomain;
+
+    type Measurement = R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        let contains = self.function.with_preimage(domain, |s| {
+            self.point
+                .with_point_subset(|p| F::Domain::subset_includes(s, p))
+        });
+        &if contains { self.weight } else { R::zero() }
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        R::normalize_static([self.weight])?;
+        Some(Self {
+            weight: R::one(),
+            ..self.clone()
+        })
+    }
+}
@@ -90,3 +90,60 @@ pub trait DiracMeasure<'subset>: Measure<'subset> {
     /// Constructs a Dirac measure at the given point.
     fn dirac(point: &Self::Space) -> Self;
 }
+
+/// Describes a type of measure on a discrete space which is determined by the
+/// mass it assigns to each point.
+pub trait FiniteMeasure<'subset>: Measure<'subset> {
+    /// Constructs the measure which assigns the mass `mass(x)` to each point
+    /// `x`.
+    ///
+    /// ```
+    /// use mes::{boolean::BoolMeasure, FiniteMeasure, PointMeasure};
+    ///
+    /// let m = BoolMeasure::from_point_masses(|x| if *x { 0.25f64 } else { 0.5 });
+    /// assert_eq!(m.with_measure_at(&true, |x| *x), 0.25);
+    /// ```
+    fn from_point_masses(mass: impl FnMut(&Self::Space) -> Self::R) -> Self;
+}
+
+/// Describes a type of measure whose conditional probability measures have a
+/// closed form.
+///
+/// Any measure can be conditioned with [`condition`](crate::condition), but
+/// this gives a [`ConditionedMeasure`](crate::ConditionedMeasure) wrapping the
+/// original measure and event.
+pub trait ConditionableMeasure<'subset>: Measure<'subset> {
+    /// Conditions the measure on the given event.
+    ///
+    /// Returns [`None`] if the event has zero measure.
+    ///
+    /// ```
+    /// use mes::{boolean::BoolMeasure, ConditionableMeasure, Measurable, Measure, PointMeasurable};
+    ///
+    /// let m = BoolMeasure {
+    ///     true_value: 0.25f64,
+    ///     false_value: 0.75f64,
+    /// };
+    ///
+    /// assert!(bool::with_full_subset(|s| m.condition(s)) == m.normalize());
+    /// assert!(true.with_point_subset(|s| m.condition(s)).is_some());
+    /// ```
+    fn condition(&self, event: &<Self::Space as Measurable>::Subset<'_>) -> Option<Self::PMeasure>;
+}
+
+/// Describes a measure against which functions can be integrated exactly.
+pub trait IntegrableMeasure<'subset>: Measure<'subset> {
+    /// Computes the integral of `f` with respect to the measure.
+    ///
+    /// ```
+    /// use mes::{boolean::BoolMeasure, IntegrableMeasure};
+    ///
+    /// let m = BoolMeasure {
+    ///     true_value: 0.25f64,
+    ///     false_value: 0.75f64,
+    /// };
+    ///
+    /// assert_eq!(m.integrate(|x| if *x { 4.0 } else { 2.0 }), 2.5);
+    /// ```
+    fn integrate(&self, f: impl FnMut(&Self::Space) -> Self::R) -> Self::R;
+}
@@ -0,0 +1,380 @@
+use core::ops::{Mul, MulAssign};
+use num_traits::{float::FloatCore, Zero};
+use with_locals::with;
+
+#[cfg(feature = "alloc")]
+use {
+    alloc::vec::Vec,
+    core::ops::{Add, AddAssign},
+};
+
+use crate::{IntegrableMeasure, Measurable, Measure, PointMeasure};
+
+/// Computes the sum of the measures of `domain` under each of the components.
+fn measure_sum<'subset, 'a, M: Measure<'subset> + 'a>(
+    components: impl Iterator<Item = &'a M>,
+    domain: &<M::Space as Measurable>::Subset<'_>,
+) -> M::R
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    let mut result = M::R::zero();
+    for m in components {
+        result += m.with_measure(M::Space::subset_upcast(domain), |x| (*x).into());
+    }
+
+    result
+}
+
+/// Computes the sum of the point measures at `value` under each of the
+/// components.
+fn measure_at_sum<'subset, 'a, M: PointMeasure<'subset> + 'a>(
+    components: impl Iterator<Item = &'a M>,
+    value: &M::Space,
+) -> M::R
+where
+    M::PointMeasurement: Copy + Into<M::R>,
+{
+    let mut result = M::R::zero();
+    for m in components {
+        result += m.with_measure_at(value, |x| (*x).into());
+    }
+
+    result
+}
+
+/// Computes the sum of the integrals of `f` with respect to each of the
+/// components.
+fn integral_sum<'subset, 'a, M: IntegrableMeasure<'subset> + 'a>(
+    components: impl Iterator<Item = &'a M>,
+    f: &mut impl FnMut(&M::Space) -> M::R,
+) -> M::R {
+    let mut result = M::R::zero();
+    for m in components {
+        result += m.integrate(&mut *f);
+    }
+
+    result
+}
+
+/// Computes the factor by which the components must be scaled for their sum to
+/// be a probability measure, or [`None`] if the total weight is zero.
+fn normalization_factor<'subset, 'a, M: Measure<'subset> + 'a>(
+    components: impl Iterator<Item = &'a M>,
+) -> Option<M::R>
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    let total = M::Space::with_full_subset(|s| measure_sum(components, s));
+    let factor = total.recip();
+    factor.is_finite().then_some(factor)
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq)]
+/// A finite mixture of measures, i.e., their sum.
+///
+/// Each component carries its own weight, so a weighted mixture is formed by
+/// scaling the components:
+/// ```
+/// use mes::{real::dirac::Dirac, Measurable, Measure, Mixture};
+///
+/// let m: Mixture<_> = [0.0f64, 1.0, 2.0]
+///     .into_iter()
+///     .map(|point| Dirac { point, weight: 0.5 })
+///     .collect();
+///
+/// let total = f64::with_full_subset(|s| m.with_measure(f64::subset_upcast(s), |x| *x));
+/// assert_eq!(total, 1.5);
+///
+/// let p = m.normalize().unwrap();
+/// let total = f64::with_full_subset(|s| p.with_measure(f64::subset_upcast(s), |x| *x));
+/// assert_eq!(total, 1.0);
+/// ```
+pub struct Mixture<M> {
+    /// The components of the mixture.
+    pub components: Vec<M>,
+}
+
+#[cfg(feature = "alloc")]
+impl<M> Mixture<M> {
+    /// Constructs an empty mixture, which is the zero measure.
+    pub fn new() -> Self {
+        Self {
+            components: Vec::new(),
+        }
+    }
+
+    /// Adds a component to the mixture.
+    pub fn push(&mut self, component: M) {
+        self.components.push(component);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> Default for Mixture<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> From<Vec<M>> for Mixture<M> {
+    fn from(components: Vec<M>) -> Self {
+        Self { components }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> FromIterator<M> for Mixture<M> {
+    fn from_iter<I: IntoIterator<Item = M>>(iter: I) -> Self {
+        Self {
+            components: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> Extend<M> for Mixture<M> {
+    fn extend<I: IntoIterator<Item = M>>(&mut self, iter: I) {
+        self.components.extend(iter);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> Add for Mixture<M> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> AddAssign for Mixture<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.components.extend(rhs.components);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Copy, M: MulAssign<R>> Mul<R> for Mixture<M> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Copy, M: MulAssign<R>> MulAssign<R> for Mixture<M> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.components.iter_mut().for_each(|m| *m *= rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'subset, M: Measure<'subset> + Clone> Measure<'subset> for Mixture<M>
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    type R = M::R;
+
+    type Space = M::Space;
+
+    type Measurement = M::R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &measure_sum(self.components.iter(), domain)
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        Some(self.clone() * normalization_factor(self.components.iter())?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'subset, M: PointMeasure<'subset> + Clone> PointMeasure<'subset> for Mixture<M>
+where
+    M::Measurement: Copy + Into<M::R>,
+    M::PointMeasurement: Copy + Into<M::R>,
+{
+    type PointMeasurement = M::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &measure_at_sum(self.components.iter(), value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<'subset, M: IntegrableMeasure<'subset> + Clone> IntegrableMeasure<'subset> for Mixture<M>
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        integral_sum(self.components.iter(), &mut f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A finite mixture of at most `N` measures, i.e., their sum.
+///
+/// Unlike [`Mixture`], this does not allocate:
+/// ```
+/// use mes::{boolean::BoolMeasure, ArrayMixture, PointMeasure};
+///
+/// let mut m = ArrayMixture::<_, 2>::new();
+/// for (t, f) in [(0.5f64, 0.0), (0.25, 0.25)] {
+///     assert!(m
+///         .push(BoolMeasure {
+///             true_value: t,
+///             false_value: f,
+///         })
+///         .is_ok());
+/// }
+/// assert!(m
+///     .push(BoolMeasure {
+///         true_value: 1.0,
+///         false_value: 1.0,
+///     })
+///     .is_err());
+///
+/// assert_eq!(m.with_measure_at(&true, |x| *x), 0.75);
+/// ```
+pub struct ArrayMixture<M, const N: usize> {
+    components: [Option<M>; N],
+}
+
+impl<M, const N: usize> ArrayMixture<M, N> {
+    /// Constructs an empty mixture, which is the zero measure.
+    pub fn new() -> Self {
+        Self {
+            components: [(); N].map(|_| None),
+        }
+    }
+
+    /// Adds a component to the mixture.
+    ///
+    /// Returns the component back if the mixture already has `N` components.
+    pub fn push(&mut self, component: M) -> Result<(), M> {
+        match self.components.iter_mut().find(|m| m.is_none()) {
+            Some(slot) => {
+                *slot = Some(component);
+                Ok(())
+            }
+            None => Err(component),
+        }
+    }
+
+    /// Iterates over the components of the mixture.
+    pub fn components(&self) -> impl Iterator<Item = &M> + '_ {
+        self.components.iter().flatten()
+    }
+
+    /// The number of components in the mixture.
+    pub fn len(&self) -> usize {
+        self.components().count()
+    }
+
+    /// Checks whether the mixture has no components.
+    pub fn is_empty(&self) -> bool {
+        self.components().next().is_none()
+    }
+}
+
+impl<M, const N: usize> Default for ArrayMixture<M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M, const N: usize> From<[M; N]> for ArrayMixture<M, N> {
+    fn from(components: [M; N]) -> Self {
+        Self {
+            components: components.map(Some),
+        }
+    }
+}
+
+impl<R: Copy, M: MulAssign<R>, const N: usize> Mul<R> for ArrayMixture<M, N> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<R: Copy, M: MulAssign<R>, const N: usize> MulAssign<R> for ArrayMixture<M, N> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.components.iter_mut().flatten().for_each(|m| *m *= rhs);
+    }
+}
+
+impl<'subset, M: Measure<'subset> + Clone, const N: usize> Measure<'subset> for ArrayMixture<M, N>
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    type R = M::R;
+
+    type Space = M::Space;
+
+    type Measurement = M::R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &measure_sum(self.components(), domain)
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        Some(self.clone() * normalization_factor(self.components())?)
+    }
+}
+
+impl<'subset, M: PointMeasure<'subset> + Clone, const N: usize> PointMeasure<'subset>
+    for ArrayMixture<M, N>
+where
+    M::Measurement: Copy + Into<M::R>,
+    M::PointMeasurement: Copy + Into<M::R>,
+{
+    type PointMeasurement = M::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &measure_at_sum(self.components(), value)
+    }
+}
+
+impl<'subset, M: IntegrableMeasure<'subset> + Clone, const N: usize> IntegrableMeasure<'subset>
+    for ArrayMixture<M, N>
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        integral_sum(self.components(), &mut f)
+    }
+}
@@ -1,23 +1,46 @@
 use core::marker::PhantomData;
 use with_locals::with;
 
+#[cfg(feature = "alloc")]
+mod categorical;
 mod compose;
+mod condition;
+mod dynamic;
+mod finite;
+mod kernel;
 mod measure;
+mod mixture;
+#[cfg(feature = "alloc")]
+mod owned;
 
+#[cfg(feature = "alloc")]
+pub use categorical::*;
 pub use compose::*;
+pub use condition::*;
+pub use dynamic::*;
+pub use finite::*;
+pub use kernel::*;
 pub use measure::*;
+pub use mixture::*;
+#[cfg(feature = "alloc")]
+pub use owned::*;
 
 use crate::util::{iter::LocalIterator, proxy::Proxy, LGType};
 
 /// A measurable space.
+///
+/// Since the continuations passed to the CPS methods below are generic over
+/// the lifetime of the subset they receive, and `Self` must outlive it, only
+/// `'static` types can currently implement this trait. In particular, spaces
+/// that borrow data cannot.
 pub trait Measurable {
     /// The type representing measurable subsets of [`Self`].
     type Subset<'a>: ?Sized + 'a
     where
         Self: 'a;
 
-    /// Upcasts a [`Self::Subset`] reference by lifetime. Essentially a proof
-    /// that [`Self::Subset`] is covariant with respect to `'a`.
+    /// Upcasts a [`Self::Subset`] reference by lifetime, which is possible
+    /// since [`Self::Subset`] is covariant with respect to `'a`.
     ///
     /// Inspired by:
     /// <https://internals.rust-lang.org/t/variance-of-lifetime-arguments-in-gats/14769/19>
@@ -72,6 +95,43 @@ pub trait Measurable {
     /// Checks whether the given subset is the empty subset.
     fn subset_is_empty(s: &Self::Subset<'_>) -> bool;
 
+    /// Checks whether the given subset is the full subset.
+    fn subset_is_full(s: &Self::Subset<'_>) -> bool {
+        Self::with_subset_complement(s, |c| Self::subset_is_empty(c))
+    }
+
+    /// Checks whether the subset `s` includes the subset `t` (i.e., whether `t`
+    /// is a subset of `s`).
+    ///
+    /// ```
+    /// use mes::{Measurable, PointMeasurable};
+    /// use with_locals::with;
+    ///
+    /// #[with]
+    /// fn main() {
+    ///     let s1: &'ref _ = bool::full_subset();
+    ///     let s2: &'ref _ = true.point_subset();
+    ///     assert!(bool::subset_includes(s1, s2));
+    ///     assert!(!bool::subset_includes(s2, s1));
+    /// }
+    /// ```
+    fn subset_includes(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+        Self::with_subset_complement(s, |c| {
+            let proxies = [
+                Proxy::new(Self::subset_upcast(c)),
+                Proxy::new(Self::subset_upcast(t)),
+            ];
+            Self::with_subset_intersect(subset_proxies::<Self>(&proxies), |d| {
+                Self::subset_is_empty(d)
+            })
+        })
+    }
+
+    /// Checks whether two subsets are equal.
+    fn subset_eq(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+        Self::subset_includes(s, t) && Self::subset_includes(t, s)
+    }
+
     #[with]
     /// Computes the complement of a subset.
     ///
@@ -97,15 +157,15 @@ pub trait Measurable {
     /// This function uses continuation-passing style (CPS) via [`with_locals`].
     /// Use it like so:
     /// ```
-    /// use mes::{Measurable, PointMeasurable};
+    /// use mes::{subset_proxies, util::proxy::Proxy, Measurable, PointMeasurable};
     /// use with_locals::with;
     ///
     /// #[with]
     /// fn main() {
     ///     let s1: &'ref _ = true.point_subset();
     ///     let s2: &'ref _ = false.point_subset();
-    ///     let s: &'ref _ =
-    ///         bool::subset_union([bool::subset_upcast(s1), bool::subset_upcast(s2)].into_iter());
+    ///     let proxies = [Proxy::new(s1), Proxy::new(s2)];
+    ///     let s: &'ref _ = bool::subset_union(subset_proxies::<bool>(&proxies));
     ///     // `s` contains a reference to the subset union.
     ///     assert!(s.includes_true && s.includes_false);
     /// }
@@ -116,35 +176,136 @@ pub trait Measurable {
     where
         Self: 'a;
 
-    #[with]
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
     /// Computes the intersection of a finite collection of subsets.
     ///
+    /// The default implementation applies De Morgan's laws to
+    /// [`Self::subset_union`] and [`Self::subset_complement`].
+    ///
     /// This function uses continuation-passing style (CPS) via [`with_locals`].
     /// Use it like so:
-    /// ```no_run
-    /// use mes::{Measurable, PointMeasurable};
+    /// ```
+    /// use mes::{subset_proxies, util::proxy::Proxy, Measurable, PointMeasurable};
     /// use with_locals::with;
     ///
     /// #[with]
     /// fn main() {
     ///     let s1: &'ref _ = true.point_subset();
-    ///     let s2: &'ref _ = false.point_subset();
-    ///     let s: &'ref _ =
-    ///         bool::subset_intersect([bool::subset_upcast(s1), bool::subset_upcast(s2)].into_iter());
+    ///     let s2: &'ref _ = bool::full_subset();
+    ///     let proxies = [Proxy::new(s1), Proxy::new(s2)];
+    ///     let s: &'ref _ = bool::subset_intersect(subset_proxies::<bool>(&proxies));
     ///     // `s` contains a reference to the subset intersection.
+    ///     assert!(s.includes_true && !s.includes_false);
     /// }
     /// ```
     fn subset_intersect<'a>(
-        subsets: impl Iterator<Item = &'a Self::Subset<'a>> + Clone + 'a,
+        subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
     ) -> &'ref Self::Subset<'ref>
     where
         Self: 'a,
     {
-        // let result: &'ref _ = Self::subset_union(subsets.m)
-        todo!()
+        let mut ret = Some(ret);
+        let mut result = None;
+        intersect_by_complement::<Self>(subsets, &mut |s| {
+            result = Some(ret.take().expect("continuation called multiple times!")(s))
+        });
+
+        result.expect("continuation was never called!")
+    }
+
+    #[with]
+    /// Computes the difference of two subsets, i.e., the subset of points which
+    /// are in `s` but not in `t`.
+    ///
+    /// This function uses continuation-passing style (CPS) via [`with_locals`].
+    /// Use it like so:
+    /// ```
+    /// use mes::{Measurable, PointMeasurable};
+    /// use with_locals::with;
+    ///
+    /// #[with]
+    /// fn main() {
+    ///     let s1: &'ref _ = bool::full_subset();
+    ///     let s2: &'ref _ = true.point_subset();
+    ///     let s: &'ref _ = bool::subset_difference(s1, s2);
+    ///     // `s` contains a reference to the subset difference.
+    ///     assert!(!s.includes_true && s.includes_false);
+    /// }
+    /// ```
+    fn subset_difference(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
+        let c: &'ref _ = Self::subset_complement(t);
+        let proxies = [
+            Proxy::new(Self::subset_upcast(s)),
+            Proxy::new(Self::subset_upcast(c)),
+        ];
+        let result: &'ref _ = Self::subset_intersect(subset_proxies::<Self>(&proxies));
+        result
+    }
+
+    #[with]
+    /// Computes the symmetric difference of two subsets, i.e., the subset of
+    /// points which are in exactly one of `s` and `t`.
+    ///
+    /// This function uses continuation-passing style (CPS) via [`with_locals`].
+    /// Use it like so:
+    /// ```
+    /// use mes::{Measurable, PointMeasurable};
+    /// use with_locals::with;
+    ///
+    /// #[with]
+    /// fn main() {
+    ///     let s1: &'ref _ = bool::full_subset();
+    ///     let s2: &'ref _ = true.point_subset();
+    ///     let s: &'ref _ = bool::subset_symmetric_difference(s1, s2);
+    ///     // `s` contains a reference to the symmetric difference.
+    ///     assert!(!s.includes_true && s.includes_false);
+    /// }
+    /// ```
+    fn subset_symmetric_difference(
+        s: &Self::Subset<'_>,
+        t: &Self::Subset<'_>,
+    ) -> &'ref Self::Subset<'ref> {
+        let d1: &'ref _ = Self::subset_difference(s, t);
+        let d2: &'ref _ = Self::subset_difference(t, s);
+        let proxies = [
+            Proxy::new(Self::subset_upcast(d1)),
+            Proxy::new(Self::subset_upcast(d2)),
+        ];
+        let result: &'ref _ = Self::subset_union(subset_proxies::<Self>(&proxies));
+        result
     }
 }
 
+/// Computes the intersection of a collection of subsets in terms of union and
+/// complement, passing the result to `f`.
+fn intersect_by_complement<'a, 'f, T: Measurable + ?Sized + 'a + 'f>(
+    mut subsets: impl LocalIterator<Item = SubsetProxy<'a, T>> + Clone + 'a,
+    f: &'f mut (dyn for<'b> FnMut(&'b T::Subset<'b>) + 'f),
+) {
+    let mut rest = subsets.clone();
+    if rest.next().is_none() {
+        return T::with_full_subset(|s| f(T::subset_upcast(s)));
+    }
+
+    let first = subsets.next().unwrap();
+    first.with_access(|s1| {
+        intersect_by_complement::<T>(rest, &mut |s2| {
+            T::with_subset_complement(T::subset_upcast(s1), |c1| {
+                T::with_subset_complement(s2, |c2| {
+                    let proxies = [
+                        Proxy::new(T::subset_upcast(c1)),
+                        Proxy::new(T::subset_upcast(c2)),
+                    ];
+                    T::with_subset_union(subset_proxies::<T>(&proxies), |u| {
+                        T::with_subset_complement(u, |s| f(T::subset_upcast(s)))
+                    })
+                })
+            })
+        })
+    })
+}
+
 /// A measurable space for which subsets containing a single point exist for all
 /// points in the space.
 pub trait PointMeasurable: Measurable {
@@ -204,7 +365,38 @@ pub struct SubsetProxy<'context, T: Measurable + ?Sized + 'context>(
 );
 
 impl<'context, T: Measurable + ?Sized + 'context> LGType for SubsetProxy<'context, T> {
-    type Type<'a> = Proxy<'a, T::Subset<'a>>
+    type Type<'a> = Proxy<'context, T::Subset<'context>>
     where
         Self: 'a;
 }
+
+/// A [`LocalIterator`] over a slice of subset proxies, as accepted by
+/// [`Measurable::subset_union`] and [`Measurable::subset_intersect`].
+pub struct SubsetProxies<'a, T: Measurable + ?Sized + 'a> {
+    proxies: &'a [Proxy<'a, T::Subset<'a>>],
+}
+
+impl<'a, T: Measurable + ?Sized + 'a> Clone for SubsetProxies<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: Measurable + ?Sized + 'a> Copy for SubsetProxies<'a, T> {}
+
+impl<'a, T: Measurable + ?Sized + 'a> LocalIterator for SubsetProxies<'a, T> {
+    type Item = SubsetProxy<'a, T>;
+
+    fn next(&mut self) -> Option<&'_ <Self::Item as LGType>::Type<'_>> {
+        let (head, tail) = self.proxies.split_first()?;
+        self.proxies = tail;
+        Some(head)
+    }
+}
+
+/// Constructs a [`LocalIterator`] over a slice of subset proxies.
+pub fn subset_proxies<'a, T: Measurable + ?Sized + 'a>(
+    proxies: &'a [Proxy<'a, T::Subset<'a>>],
+) -> SubsetProxies<'a, T> {
+    SubsetProxies { proxies }
+}
@@ -0,0 +1,264 @@
+use alloc::vec::Vec;
+use with_locals::with;
+
+use crate::{subset_proxies, util::proxy::Proxy, Measurable, MeasurableFn, PointMeasurable};
+
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// An owned subset of a measurable space.
+///
+/// Subsets produced by the continuation-passing methods of [`Measurable`] may
+/// borrow data which only lives as long as the continuation. An
+/// [`OwnedSubset`] instead stores the subset in the owned representation of its
+/// space (see [`OwnedMeasurable::Owned`]), so that it can be stored in structs
+/// and returned from functions. The subset is computed once, when the
+/// [`OwnedSubset`] is constructed, and can then be accessed any number of
+/// times.
+pub struct OwnedSubset<T: OwnedMeasurable + ?Sized>(T::Owned);
+
+impl<T: OwnedMeasurable + ?Sized> Clone for OwnedSubset<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: OwnedMeasurable + ?Sized> OwnedSubset<T> {
+    /// Copies the given subset into an owned subset, or returns [`None`] if
+    /// the subset has no owned representation (see
+    /// [`OwnedMeasurable::subset_to_owned`]).
+    pub fn new(s: &T::Subset<'_>) -> Option<Self> {
+        T::subset_to_owned(s).map(Self)
+    }
+
+    /// Copies a subset built by the methods of [`Measurable`], which always has
+    /// an owned representation.
+    fn built(s: &T::Subset<'_>) -> Self {
+        Self::new(s).expect("subsets built by `Measurable` must have an owned representation")
+    }
+
+    /// Constructs an owned subset from its owned representation.
+    pub fn from_owned(owned: T::Owned) -> Self {
+        Self(owned)
+    }
+
+    /// The owned representation of the subset.
+    pub fn as_owned(&self) -> &T::Owned {
+        &self.0
+    }
+
+    #[with]
+    /// Accesses the subset.
+    ///
+    /// This function uses continuation-passing style (CPS) via [`with_locals`].
+    /// Use it like so:
+    /// ```
+    /// use mes::{boolean::BoolMeasure, Measurable, Measure, OwnedMeasurable, PointMeasurable};
+    /// use with_locals::with;
+    ///
+    /// #[with]
+    /// fn main() {
+    ///     let event = true.point_subset_owned();
+    ///     let m = BoolMeasure {
+    ///         true_value: 0.25f32,
+    ///         false_value: 0.75f32,
+    ///     };
+    ///     let s: &'ref _ = event.access();
+    ///     let x: &'ref _ = m.measure(bool::subset_upcast(s));
+    ///     assert_eq!(*x, 0.25);
+    /// }
+    /// ```
+    pub fn access(&self) -> &'ref T::Subset<'ref> {
+        let s: &'ref _ = T::subset_from_owned(&self.0);
+        s
+    }
+}
+
+/// A continuation receiving a subset of `T`.
+type SubsetFn<'f, T> = dyn for<'b> FnMut(&'b <T as Measurable>::Subset<'b>) + 'f;
+
+/// Computes `op` over a collection of owned subsets, combining them pairwise,
+/// passing the result to `f`. The empty collection yields `empty`.
+fn fold_owned<'f, T: OwnedMeasurable + ?Sized + 'f>(
+    subsets: &[OwnedSubset<T>],
+    empty: fn(&mut SubsetFn<'_, T>),
+    op: fn(&T::Subset<'_>, &T::Subset<'_>, &mut SubsetFn<'_, T>),
+    f: &'f mut SubsetFn<'f, T>,
+) {
+    match subsets {
+        [] => empty(f),
+        [s] => s.with_access(|s| f(T::subset_upcast(s))),
+        [first, rest @ ..] => first.with_access(|s1| {
+            fold_owned(rest, empty, op, &mut |s2| op(T::subset_upcast(s1), s2, f))
+        }),
+    }
+}
+
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// A measurable space whose subsets have an owned representation, allowing
+/// them to be stored as [`OwnedSubset`]s.
+///
+/// ```
+/// use mes::{
+///     real::{
+///         interval::{Interval, IntervalSet},
+///         RealSubset,
+///     },
+///     OwnedMeasurable, OwnedSubset, PointMeasurable,
+/// };
+///
+/// struct Events {
+///     outside: OwnedSubset<f64>,
+///     punctured: OwnedSubset<f64>,
+/// }
+///
+/// fn events() -> Events {
+///     let unit = OwnedSubset::<f64>::new(&Interval::closed(0.0, 1.0)).unwrap();
+///     Events {
+///         outside: f64::subset_complement_owned(&unit),
+///         punctured: f64::subset_difference_owned(&unit, &0.5.point_subset_owned()),
+///     }
+/// }
+///
+/// let events = events();
+/// assert_eq!(
+///     *events.outside.as_owned(),
+///     !IntervalSet::from(Interval::closed(0.0, 1.0))
+/// );
+/// assert!(events
+///     .punctured
+///     .with_access(|s| s.contains(&0.25) && !s.contains(&0.5)));
+/// ```
+pub trait OwnedMeasurable: Measurable {
+    /// The owned representation of subsets of [`Self`].
+    type Owned: Clone;
+
+    /// Copies a subset into its owned representation, or returns [`None`] if
+    /// it has none.
+    ///
+    /// Implementations must succeed on the subsets built by the methods of
+    /// [`Measurable`] and [`PointMeasurable`] from owned subsets, which the
+    /// `*_owned` methods of this trait rely on.
+    fn subset_to_owned(s: &Self::Subset<'_>) -> Option<Self::Owned>;
+
+    #[with]
+    /// Accesses a subset through its owned representation.
+    fn subset_from_owned(s: &Self::Owned) -> &'ref Self::Subset<'ref>;
+
+    /// Computes the empty subset as an owned subset.
+    fn empty_subset_owned() -> OwnedSubset<Self> {
+        Self::with_empty_subset(|s| OwnedSubset::built(s))
+    }
+
+    /// Computes the full subset as an owned subset.
+    fn full_subset_owned() -> OwnedSubset<Self> {
+        Self::with_full_subset(|s| OwnedSubset::built(s))
+    }
+
+    /// Computes the complement of an owned subset.
+    fn subset_complement_owned(s: &OwnedSubset<Self>) -> OwnedSubset<Self> {
+        s.with_access(|s| Self::with_subset_complement(s, |c| OwnedSubset::built(c)))
+    }
+
+    /// Computes the union of a finite collection of owned subsets.
+    fn subset_union_owned(
+        subsets: impl IntoIterator<Item = OwnedSubset<Self>>,
+    ) -> OwnedSubset<Self> {
+        let subsets: Vec<_> = subsets.into_iter().collect();
+        let mut result = None;
+        fold_owned(
+            &subsets,
+            |f| Self::with_empty_subset(|s| f(Self::subset_upcast(s))),
+            |s, t, f| {
+                let proxies = [
+                    Proxy::new(Self::subset_upcast(s)),
+                    Proxy::new(Self::subset_upcast(t)),
+                ];
+                Self::with_subset_union(subset_proxies::<Self>(&proxies), |u| {
+                    f(Self::subset_upcast(u))
+                })
+            },
+            &mut |u| result = Some(OwnedSubset::built(u)),
+        );
+
+        result.expect("continuation was never called!")
+    }
+
+    /// Computes the intersection of a finite collection of owned subsets.
+    fn subset_intersect_owned(
+        subsets: impl IntoIterator<Item = OwnedSubset<Self>>,
+    ) -> OwnedSubset<Self> {
+        let subsets: Vec<_> = subsets.into_iter().collect();
+        let mut result = None;
+        fold_owned(
+            &subsets,
+            |f| Self::with_full_subset(|s| f(Self::subset_upcast(s))),
+            |s, t, f| {
+                let proxies = [
+                    Proxy::new(Self::subset_upcast(s)),
+                    Proxy::new(Self::subset_upcast(t)),
+                ];
+                Self::with_subset_intersect(subset_proxies::<Self>(&proxies), |i| {
+                    f(Self::subset_upcast(i))
+                })
+            },
+            &mut |i| result = Some(OwnedSubset::built(i)),
+        );
+
+        result.expect("continuation was never called!")
+    }
+
+    /// Computes the difference of two owned subsets.
+    fn subset_difference_owned(s: &OwnedSubset<Self>, t: &OwnedSubset<Self>) -> OwnedSubset<Self> {
+        s.with_access(|s| {
+            t.with_access(|t| Self::with_subset_difference(s, t, |d| OwnedSubset::built(d)))
+        })
+    }
+
+    /// Computes the symmetric difference of two owned subsets.
+    fn subset_symmetric_difference_owned(
+        s: &OwnedSubset<Self>,
+        t: &OwnedSubset<Self>,
+    ) -> OwnedSubset<Self> {
+        s.with_access(|s| {
+            t.with_access(|t| {
+                Self::with_subset_symmetric_difference(s, t, |d| OwnedSubset::built(d))
+            })
+        })
+    }
+
+    /// Computes the subset containing the given point as an owned subset.
+    fn point_subset_owned(&self) -> OwnedSubset<Self>
+    where
+        Self: PointMeasurable,
+    {
+        self.with_point_subset(|s| OwnedSubset::built(s))
+    }
+}
+
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// Extends [`MeasurableFn`] with methods producing [`OwnedSubset`]s.
+pub trait OwnedMeasurableFn<'subset>: MeasurableFn<'subset>
+where
+    Self::Domain: OwnedMeasurable,
+    Self::Codomain: OwnedMeasurable,
+{
+    /// Computes the preimage of the given owned subset of the codomain, or
+    /// returns [`None`] if the preimage has no owned representation.
+    fn preimage_owned<'a>(
+        &'a self,
+        s: &OwnedSubset<Self::Codomain>,
+    ) -> Option<OwnedSubset<Self::Domain>>
+    where
+        'subset: 'a,
+    {
+        s.with_access(|s| {
+            self.with_preimage(Self::Codomain::subset_upcast(s), |p| OwnedSubset::new(p))
+        })
+    }
+}
+
+impl<'subset, F: MeasurableFn<'subset> + ?Sized> OwnedMeasurableFn<'subset> for F
+where
+    F::Domain: OwnedMeasurable,
+    F::Codomain: OwnedMeasurable,
+{
+}
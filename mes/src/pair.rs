@@ -1,26 +1,116 @@
 //! Implementation of binary Cartesian products as measurable spaces.
+//!
+//! Pairs nest, so that e.g. `((f64, bool), ())` is a measurable space whose
+//! subsets and measures are built from those of its components:
+//! ```
+//! use mes::{
+//!     boolean::BoolMeasure, pair::ProductMeasure, real::dirac::Dirac, unit::UnitMeasure,
+//!     Measurable, Measure, PointMeasurable,
+//! };
+//!
+//! type Space = ((f64, bool), ());
+//!
+//! let m = ProductMeasure {
+//!     left: ProductMeasure {
+//!         left: Dirac {
+//!             point: 0.5f64,
+//!             weight: 2.0,
+//!         },
+//!         right: BoolMeasure {
+//!             true_value: 0.25,
+//!             false_value: 0.75,
+//!         },
+//!     },
+//!     right: UnitMeasure { weight: 1.0 },
+//! };
+//!
+//! ((0.5, true), ()).with_point_subset(|p| {
+//!     let p = Space::subset_upcast(p);
+//!     assert!(!Space::subset_is_empty(p));
+//!     assert_eq!(m.with_measure(p, |x| *x), 0.5);
+//!
+//!     Space::with_full_subset(|s| {
+//!         let s = Space::subset_upcast(s);
+//!         assert!(Space::subset_includes(s, p));
+//!         assert!(!Space::subset_includes(p, s));
+//!         assert_eq!(m.with_measure(s, |x| *x), 2.0);
+//!     });
+//!
+//!     Space::with_subset_complement(p, |c| {
+//!         let c = Space::subset_upcast(c);
+//!         assert!(!Space::subset_includes(c, p));
+//!         assert_eq!(m.with_measure(c, |x| *x), 1.5);
+//!     });
+//! });
+//! ```
 
-use derive_more::{Add, AddAssign, Mul, MulAssign};
+use core::ops::{Mul, MulAssign};
+use num_traits::{float::FloatCore, Zero};
 use type_variance::{Contravariant, Invariant};
 use with_locals::with;
 
 use crate::{
     real::Real,
-    util::{
-        iter::{LocalIterator, LocalIteratorExt, Map, SliceExt},
-        proxy::Proxy,
-    },
-    DiracMeasure, Measurable, MeasurableFn, Measure, PointMeasurable, PointMeasure, SubsetProxy,
+    subset_proxies,
+    util::{iter::LocalIterator, proxy::Proxy},
+    DiracMeasure, FiniteMeasurable, FiniteMeasure, IntegrableMeasure, Kernel, Measurable,
+    MeasurableFn, Measure, PointMeasurable, PointMeasure, SubsetProxy,
 };
 
-#[derive(Debug, PartialEq, Eq)]
-/// A subset of [`bool`].
-pub struct PairSubset<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> {
-    /// The subset of `T`.
-    pub left: &'a T::Subset<'a>,
+#[cfg(feature = "alloc")]
+use {crate::OwnedMeasurable, alloc::vec::Vec};
+
+/// A subset of `(T, U)`, represented as a finite union of disjoint rectangles.
+///
+/// The rectangles are linked together through [`DynPairSubset`] references, so
+/// that subsets can be built up in continuation-passing style without
+/// allocating. The subsets of `(T, U)` as a [`Measurable`] space are
+/// `dyn DynPairSubset<T, U>` trait objects, to which references to a
+/// [`PairSubset`] coerce.
+pub enum PairSubset<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> {
+    /// The empty subset.
+    Empty,
+
+    /// The union of the rectangle `left × right` with the subset `rest`, which
+    /// must be disjoint from the rectangle.
+    Union {
+        /// The subset of `T` spanned by the rectangle.
+        left: &'a T::Subset<'a>,
 
-    /// The subset of `U`.
-    pub right: &'a U::Subset<'a>,
+        /// The subset of `U` spanned by the rectangle.
+        right: &'a U::Subset<'a>,
+
+        /// The remaining rectangles.
+        rest: &'a (dyn DynPairSubset<T, U> + 'a),
+    },
+}
+
+impl<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> PairSubset<'a, T, U> {
+    /// Constructs the subset consisting of the single rectangle `left × right`.
+    pub fn rectangle(left: &'a T::Subset<'a>, right: &'a U::Subset<'a>) -> Self {
+        Self::Union {
+            left,
+            right,
+            rest: &PairSubset::Empty,
+        }
+    }
+
+    /// Checks whether `f` holds for every rectangle in the subset, stopping at
+    /// the first rectangle for which it does not.
+    pub fn all_rectangles(
+        &self,
+        mut f: impl FnMut(&T::Subset<'_>, &U::Subset<'_>) -> bool,
+    ) -> bool {
+        all_rectangles(self, &mut f)
+    }
+
+    /// Calls `f` on every rectangle in the subset.
+    pub fn for_each_rectangle(&self, mut f: impl FnMut(&T::Subset<'_>, &U::Subset<'_>)) {
+        all_rectangles(self, &mut |left, right| {
+            f(left, right);
+            true
+        });
+    }
 }
 
 impl<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> Clone for PairSubset<'a, T, U> {
@@ -31,11 +121,102 @@ impl<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> Clone for PairSubset<'
 
 impl<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> Copy for PairSubset<'a, T, U> {}
 
+/// An object-safe view of a [`PairSubset`] of any lifetime, which is the type
+/// of subsets of `(T, U)`.
+///
+/// Unlike a reference to a [`PairSubset`], a reference to a [`DynPairSubset`]
+/// can always be shortened, since trait objects are covariant in their lifetime
+/// bound. This is what allows rectangles to be prepended to an existing
+/// [`PairSubset`], and what makes [`Measurable::subset_upcast`] the identity:
+/// ```
+/// use mes::Measurable;
+///
+/// type Space = ((f64, bool), ());
+///
+/// fn shorten<'a, 'b: 'a>(
+///     s: &'a <Space as Measurable>::Subset<'b>,
+/// ) -> &'a <Space as Measurable>::Subset<'a> {
+///     s
+/// }
+/// ```
+pub trait DynPairSubset<T: Measurable, U: Measurable + ?Sized> {
+    /// Passes the subset to `f`.
+    fn with_subset(&self, f: &mut dyn for<'b> FnMut(&'b PairSubset<'b, T, U>));
+}
+
+impl<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> dyn DynPairSubset<T, U> + 'a {
+    /// Checks whether `f` holds for every rectangle in the subset, stopping at
+    /// the first rectangle for which it does not.
+    pub fn all_rectangles(
+        &self,
+        mut f: impl FnMut(&T::Subset<'_>, &U::Subset<'_>) -> bool,
+    ) -> bool {
+        let mut result = true;
+        self.with_subset(&mut |s| result = all_rectangles(s, &mut f));
+        result
+    }
+
+    /// Calls `f` on every rectangle in the subset.
+    ///
+    /// ```
+    /// use mes::{Measurable, PointMeasurable};
+    ///
+    /// (true, ()).with_point_subset(|p| {
+    ///     <(bool, ())>::with_subset_complement(p, |c| {
+    ///         let mut count = 0;
+    ///         c.for_each_rectangle(|left, right| {
+    ///             assert!(!left.includes_true && right.full);
+    ///             count += 1;
+    ///         });
+    ///         assert_eq!(count, 1);
+    ///     })
+    /// });
+    /// ```
+    pub fn for_each_rectangle(&self, mut f: impl FnMut(&T::Subset<'_>, &U::Subset<'_>)) {
+        self.all_rectangles(|left, right| {
+            f(left, right);
+            true
+        });
+    }
+}
+
+impl<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a> DynPairSubset<T, U>
+    for PairSubset<'a, T, U>
+{
+    fn with_subset(&self, f: &mut dyn for<'b> FnMut(&'b PairSubset<'b, T, U>)) {
+        match *self {
+            PairSubset::Empty => f(&PairSubset::Empty),
+            PairSubset::Union { left, right, rest } => f(&PairSubset::Union {
+                left: T::subset_upcast(left),
+                right: U::subset_upcast(right),
+                rest,
+            }),
+        }
+    }
+}
+
+/// Checks whether `f` holds for every rectangle in `s`.
+fn all_rectangles<'f, T: Measurable + 'f, U: Measurable + ?Sized + 'f>(
+    s: &PairSubset<'_, T, U>,
+    f: &mut (dyn FnMut(&T::Subset<'_>, &U::Subset<'_>) -> bool + 'f),
+) -> bool {
+    match *s {
+        PairSubset::Empty => true,
+        PairSubset::Union { left, right, rest } => {
+            f(left, right) && {
+                let mut result = true;
+                rest.with_subset(&mut |rest| result = all_rectangles(rest, f));
+                result
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// The left projection function from `(T, U)` to `T`.
 pub struct LeftProjection<T: Measurable, U: Measurable + ?Sized>(Invariant<T>, Contravariant<U>);
 
-impl<'subset, T: Measurable + 'static, U: Measurable + ?Sized + 'static> MeasurableFn<'subset>
+impl<'subset, T: Measurable, U: Measurable + ?Sized> MeasurableFn<'subset>
     for LeftProjection<T, U>
 {
     type Domain = (T, U);
@@ -46,15 +227,12 @@ impl<'subset, T: Measurable + 'static, U: Measurable + ?Sized + 'static> Measura
     fn preimage<'a>(
         &'a self,
         s: &'a <Self::Codomain as Measurable>::Subset<'a>,
-    ) -> &'ref <Self::Domain as Measurable>::Subset<'ref>
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref)
     where
         'subset: 'a,
     {
         let right: &'ref _ = U::full_subset();
-        &PairSubset {
-            left: T::subset_upcast(s),
-            right: U::subset_upcast(right),
-        }
+        &PairSubset::rectangle(T::subset_upcast(s), U::subset_upcast(right))
     }
 }
 
@@ -62,7 +240,7 @@ impl<'subset, T: Measurable + 'static, U: Measurable + ?Sized + 'static> Measura
 /// The right projection function from `(T, U)` to `U`.
 pub struct RightProjection<T: Measurable, U: Measurable + ?Sized>(Contravariant<T>, Invariant<U>);
 
-impl<'subset, T: Measurable + 'static, U: Measurable + ?Sized + 'static> MeasurableFn<'subset>
+impl<'subset, T: Measurable, U: Measurable + ?Sized> MeasurableFn<'subset>
     for RightProjection<T, U>
 {
     type Domain = (T, U);
@@ -73,15 +251,12 @@ impl<'subset, T: Measurable + 'static, U: Measurable + ?Sized + 'static> Measura
     fn preimage<'a>(
         &'a self,
         s: &'a <Self::Codomain as Measurable>::Subset<'a>,
-    ) -> &'ref <Self::Domain as Measurable>::Subset<'ref>
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref)
     where
         'subset: 'a,
     {
         let left: &'ref _ = T::full_subset();
-        &PairSubset {
-            left: T::subset_upcast(left),
-            right: U::subset_upcast(s),
-        }
+        &PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(s))
     }
 }
 
@@ -93,8 +268,7 @@ pub struct ForkFunction<
     F: MeasurableFn<'a, Domain = T>,
     G: MeasurableFn<'a, Domain = T>,
 > where
-    F::Codomain: Sized + 'static,
-    G::Codomain: 'static,
+    F::Codomain: Sized,
 {
     left: &'a F,
     right: &'a G,
@@ -107,14 +281,14 @@ impl<
         G: MeasurableFn<'subset, Domain = T>,
     > MeasurableFn<'subset> for ForkFunction<'subset, T, F, G>
 where
-    F::Codomain: Sized + 'static,
-    G::Codomain: 'static,
+    F::Codomain: Sized,
 {
     type Domain = T;
 
     type Codomain = (F::Codomain, G::Codomain);
 
-    #[with]
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
     fn preimage<'a>(
         &'a self,
         s: &'a <Self::Codomain as Measurable>::Subset<'a>,
@@ -122,153 +296,591 @@ where
     where
         'subset: 'a,
     {
-        let left: &'ref _ = self.left.preimage(s.left);
-        let right: &'ref _ = self.right.preimage(s.right);
-        let result: &'ref _ =
-            T::subset_intersect([T::subset_upcast(left), T::subset_upcast(right)].into_iter());
-        result
+        let mut ret = Some(ret);
+        let mut result = None;
+        fork_preimage(self, s, &mut |p| {
+            result = Some(ret.take().expect("continuation called multiple times!")(p))
+        });
+
+        result.expect("continuation was never called!")
     }
 }
 
-impl<T: Measurable + 'static, U: Measurable + ?Sized + 'static> Measurable for (T, U) {
-    type Subset<'a> = PairSubset<'a, T, U> where Self: 'a;
+/// Computes the preimage of `s` under a fork function, as the union of the
+/// intersections of the preimages of the sides of each rectangle, passing the
+/// result to `f`.
+fn fork_preimage<
+    'subset,
+    'f,
+    T: Measurable + 'f,
+    F: MeasurableFn<'subset, Domain = T>,
+    G: MeasurableFn<'subset, Domain = T>,
+>(
+    fork: &ForkFunction<'subset, T, F, G>,
+    s: &(dyn DynPairSubset<F::Codomain, G::Codomain> + '_),
+    f: &mut (dyn for<'b> FnMut(&'b T::Subset<'b>) + 'f),
+) where
+    F::Codomain: Sized,
+{
+    s.with_subset(&mut |s| match *s {
+        PairSubset::Empty => T::with_empty_subset(|e| f(T::subset_upcast(e))),
+        PairSubset::Union { left, right, rest } => {
+            fork.left
+                .with_preimage(F::Codomain::subset_upcast(left), |left| {
+                    fork.right
+                        .with_preimage(G::Codomain::subset_upcast(right), |right| {
+                            let proxies = [
+                                Proxy::new(T::subset_upcast(left)),
+                                Proxy::new(T::subset_upcast(right)),
+                            ];
+                            T::with_subset_intersect(subset_proxies::<T>(&proxies), |i| {
+                                fork_preimage(fork, rest, &mut |u| {
+                                    let proxies = [
+                                        Proxy::new(T::subset_upcast(i)),
+                                        Proxy::new(T::subset_upcast(u)),
+                                    ];
+                                    T::with_subset_union(subset_proxies::<T>(&proxies), |p| {
+                                        f(T::subset_upcast(p))
+                                    })
+                                })
+                            })
+                        })
+                })
+        }
+    })
+}
+
+// The methods below name `dyn DynPairSubset` rather than `Self::Subset` in
+// their continuations, since normalizing the latter under a higher-ranked
+// lifetime would require `T` and `U` to be `'static`.
+impl<T: Measurable, U: Measurable + ?Sized> Measurable for (T, U) {
+    type Subset<'a> = dyn DynPairSubset<T, U> + 'a where Self: 'a;
 
-    fn subset_upcast<'a, 'b: 'a>(s: &'a Self::Subset<'b>) -> &'a Self::Subset<'a> {
-        // s
-        todo!()
+    fn subset_upcast<'a, 'b: 'a>(
+        s: &'a (dyn DynPairSubset<T, U> + 'b),
+    ) -> &'a (dyn DynPairSubset<T, U> + 'a) {
+        s
     }
 
     #[with]
-    fn empty_subset() -> &'ref Self::Subset<'ref> {
-        let left: &'ref _ = T::empty_subset();
-        let right: &'ref _ = U::empty_subset();
-        &PairSubset {
-            left: T::subset_upcast(left),
-            right: U::subset_upcast(right),
-        }
+    fn empty_subset() -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
+        &PairSubset::Empty
     }
 
     #[with]
-    fn full_subset() -> &'ref Self::Subset<'ref> {
+    fn full_subset() -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
         let left: &'ref _ = T::full_subset();
         let right: &'ref _ = U::full_subset();
-        &PairSubset {
-            left: T::subset_upcast(left),
-            right: U::subset_upcast(right),
-        }
+        &PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(right))
     }
 
-    fn subset_is_empty(s: &Self::Subset<'_>) -> bool {
-        T::subset_is_empty(s.left) && U::subset_is_empty(s.right)
+    fn subset_is_empty(s: &(dyn DynPairSubset<T, U> + '_)) -> bool {
+        s.all_rectangles(|left, right| T::subset_is_empty(left) || U::subset_is_empty(right))
     }
 
-    #[with]
-    fn subset_complement(s: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
-        let PairSubset { left, right } = *s;
-        let left_c: &'ref _ = T::subset_complement(left);
-        let right_c: &'ref _ = U::subset_complement(right);
-        let result: &'ref _ = Self::subset_union(
-            [
-                Proxy::new(&PairSubset {
-                    left: T::subset_upcast(left),
-                    right: U::subset_upcast(right_c),
-                }),
-                Proxy::new(&PairSubset {
-                    left: T::subset_upcast(left_c),
-                    right: U::subset_upcast(right),
-                }),
-                Proxy::new(&PairSubset {
-                    left: T::subset_upcast(left_c),
-                    right: U::subset_upcast(right_c),
-                }),
-            ]
-            .local_iter()
-            .map(|proxy| *proxy),
-        );
+    fn subset_is_full(s: &(dyn DynPairSubset<T, U> + '_)) -> bool {
+        let mut result = false;
+        complement(s, &mut |c| result = Self::subset_is_empty(c));
         result
     }
 
-    #[with]
+    fn subset_includes(
+        s: &(dyn DynPairSubset<T, U> + '_),
+        t: &(dyn DynPairSubset<T, U> + '_),
+    ) -> bool {
+        let mut result = false;
+        difference(t, s, &mut |d| result = Self::subset_is_empty(d));
+        result
+    }
+
+    fn subset_eq(s: &(dyn DynPairSubset<T, U> + '_), t: &(dyn DynPairSubset<T, U> + '_)) -> bool {
+        Self::subset_includes(s, t) && Self::subset_includes(t, s)
+    }
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
+    fn subset_complement(
+        s: &(dyn DynPairSubset<T, U> + '_),
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
+        let mut ret = Some(ret);
+        let mut result = None;
+        complement(s, &mut |c| {
+            result = Some(ret.take().expect("continuation called multiple times!")(c))
+        });
+
+        result.expect("continuation was never called!")
+    }
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
     fn subset_union<'a>(
         subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
-    ) -> &'ref Self::Subset<'ref>
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref)
     where
         Self: 'a,
     {
-        // fn map_left<'a, 'b, T: Measurable + 'static, U: Measurable + ?Sized>(
-        //     s: &'a PairSubset<'b, T, U>,
-        //     f: &'a mut (dyn for<'c> FnMut(&'c T::Subset<'b>) + 'a),
-        // ) {
-        //     f(s.left);
-        // }
-
-        // fn map_right<'a, 'b, T: Measurable + 'static, U: Measurable + ?Sized>(
-        //     s: &'a PairSubset<'b, T, U>,
-        //     f: &'a mut (dyn for<'c> FnMut(&'c U::Subset<'b>) + 'a),
-        // ) {
-        //     f(s.right);
-        // }
-
-        let left: &'ref _ =
-            T::subset_union(subsets.clone().map(|proxy| proxy.map(&|s, f| f(s.left))));
-        let right: &'ref _ =
-            U::subset_union(subsets.clone().map(|proxy| proxy.map(&|s, f| f(s.right))));
-        &PairSubset {
-            left: T::subset_upcast(left),
-            right: U::subset_upcast(right),
+        let mut ret = Some(ret);
+        let mut result = None;
+        union_all::<T, U>(subsets, &mut |s| {
+            result = Some(ret.take().expect("continuation called multiple times!")(s))
+        });
+
+        result.expect("continuation was never called!")
+    }
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
+    fn subset_intersect<'a>(
+        subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref)
+    where
+        Self: 'a,
+    {
+        let mut ret = Some(ret);
+        let mut result = None;
+        intersect_all::<T, U>(subsets, &mut |s| {
+            result = Some(ret.take().expect("continuation called multiple times!")(s))
+        });
+
+        result.expect("continuation was never called!")
+    }
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
+    fn subset_difference(
+        s: &(dyn DynPairSubset<T, U> + '_),
+        t: &(dyn DynPairSubset<T, U> + '_),
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
+        let mut ret = Some(ret);
+        let mut result = None;
+        difference(s, t, &mut |d| {
+            result = Some(ret.take().expect("continuation called multiple times!")(d))
+        });
+
+        result.expect("continuation was never called!")
+    }
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
+    fn subset_symmetric_difference(
+        s: &(dyn DynPairSubset<T, U> + '_),
+        t: &(dyn DynPairSubset<T, U> + '_),
+    ) -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
+        let mut ret = Some(ret);
+        let mut result = None;
+        difference(s, t, &mut |d| {
+            complement(s, &mut |s_c| {
+                intersect_into(t, s_c, d, &mut |d| {
+                    result = Some(ret.take().expect("continuation called multiple times!")(d))
+                })
+            })
+        });
+
+        result.expect("continuation was never called!")
+    }
+}
+
+/// A continuation accepting a subset of `(T, U)`.
+type PairFn<'f, T, U> = dyn for<'b> FnMut(&'b (dyn DynPairSubset<T, U> + 'b)) + 'f;
+
+/// Passes the full subset of `(T, U)` to `f`.
+fn full<T: Measurable, U: Measurable + ?Sized>(f: &mut PairFn<'_, T, U>) {
+    T::with_full_subset(|left| {
+        U::with_full_subset(|right| {
+            f(&PairSubset::rectangle(
+                T::subset_upcast(left),
+                U::subset_upcast(right),
+            ))
+        })
+    })
+}
+
+/// Computes the complement of `s`, passing the result to `f`.
+///
+/// The complement of a rectangle `A × B` is the disjoint union of `Aᶜ × U` and
+/// `A × Bᶜ`, and the complement of a union is the intersection of the
+/// complements.
+fn complement<T: Measurable, U: Measurable + ?Sized>(
+    s: &(dyn DynPairSubset<T, U> + '_),
+    f: &mut PairFn<'_, T, U>,
+) {
+    s.with_subset(&mut |s| match *s {
+        PairSubset::Empty => full(f),
+        PairSubset::Union { left, right, rest } => T::with_subset_complement(left, |left_c| {
+            U::with_subset_complement(right, |right_c| {
+                U::with_full_subset(|full| {
+                    let second =
+                        PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(right_c));
+                    let first = PairSubset::Union {
+                        left: T::subset_upcast(left_c),
+                        right: U::subset_upcast(full),
+                        rest: &second,
+                    };
+                    complement(rest, &mut |rest_c| {
+                        intersect_into(&first, rest_c, &PairSubset::Empty, f)
+                    })
+                })
+            })
+        }),
+    })
+}
+
+/// Computes `s ∩ tᶜ`, passing the result to `f`.
+fn difference<T: Measurable, U: Measurable + ?Sized>(
+    s: &(dyn DynPairSubset<T, U> + '_),
+    t: &(dyn DynPairSubset<T, U> + '_),
+    f: &mut PairFn<'_, T, U>,
+) {
+    complement(t, &mut |t_c| intersect_into(s, t_c, &PairSubset::Empty, f))
+}
+
+/// Computes the disjoint union of `s ∩ t` with `acc`, passing the result to
+/// `f`.
+fn intersect_into<T: Measurable, U: Measurable + ?Sized>(
+    s: &(dyn DynPairSubset<T, U> + '_),
+    t: &(dyn DynPairSubset<T, U> + '_),
+    acc: &(dyn DynPairSubset<T, U> + '_),
+    f: &mut PairFn<'_, T, U>,
+) {
+    s.with_subset(&mut |s| match *s {
+        PairSubset::Empty => f(acc),
+        PairSubset::Union { left, right, rest } => {
+            intersect_rectangle_into(left, right, t, acc, &mut |acc| {
+                intersect_into(rest, t, acc, f)
+            })
         }
-        // todo!()
+    })
+}
+
+/// Computes the disjoint union of `(left × right) ∩ t` with `acc`, passing the
+/// result to `f`. Empty rectangles are left out of the result.
+fn intersect_rectangle_into<T: Measurable, U: Measurable + ?Sized>(
+    left: &T::Subset<'_>,
+    right: &U::Subset<'_>,
+    t: &(dyn DynPairSubset<T, U> + '_),
+    acc: &(dyn DynPairSubset<T, U> + '_),
+    f: &mut PairFn<'_, T, U>,
+) {
+    t.with_subset(&mut |t| match *t {
+        PairSubset::Empty => f(acc),
+        PairSubset::Union {
+            left: t_left,
+            right: t_right,
+            rest,
+        } => {
+            let lefts = [
+                Proxy::new(T::subset_upcast(left)),
+                Proxy::new(T::subset_upcast(t_left)),
+            ];
+            let rights = [
+                Proxy::new(U::subset_upcast(right)),
+                Proxy::new(U::subset_upcast(t_right)),
+            ];
+            T::with_subset_intersect(subset_proxies::<T>(&lefts), |l| {
+                U::with_subset_intersect(subset_proxies::<U>(&rights), |r| {
+                    if T::subset_is_empty(l) || U::subset_is_empty(r) {
+                        intersect_rectangle_into(left, right, rest, acc, f)
+                    } else {
+                        let acc = PairSubset::Union {
+                            left: T::subset_upcast(l),
+                            right: U::subset_upcast(r),
+                            rest: acc,
+                        };
+                        intersect_rectangle_into(left, right, rest, &acc, f)
+                    }
+                })
+            })
+        }
+    })
+}
+
+/// Computes the union of a collection of subsets, passing the result to `f`.
+///
+/// Each subset is added to the union of the rest as `s ⊔ (t ∩ sᶜ)`, which keeps
+/// the rectangles disjoint.
+fn union_all<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a>(
+    mut subsets: impl LocalIterator<Item = SubsetProxy<'a, (T, U)>> + Clone + 'a,
+    f: &mut PairFn<'_, T, U>,
+) {
+    let mut rest = subsets.clone();
+    if rest.next().is_none() {
+        return f(&PairSubset::Empty);
     }
+
+    let first = subsets.next().unwrap();
+    first.with_access(|s| {
+        union_all::<T, U>(rest, &mut |t| {
+            complement(s, &mut |s_c| intersect_into(t, s_c, s, f))
+        })
+    })
 }
 
-impl<T: PointMeasurable + 'static, U: PointMeasurable + ?Sized + 'static> PointMeasurable
-    for (T, U)
-{
+/// Computes the intersection of a collection of subsets, passing the result to
+/// `f`.
+fn intersect_all<'a, T: Measurable + 'a, U: Measurable + ?Sized + 'a>(
+    mut subsets: impl LocalIterator<Item = SubsetProxy<'a, (T, U)>> + Clone + 'a,
+    f: &mut PairFn<'_, T, U>,
+) {
+    let mut rest = subsets.clone();
+    if rest.next().is_none() {
+        return full(f);
+    }
+
+    let first = subsets.next().unwrap();
+    first.with_access(|s| {
+        intersect_all::<T, U>(rest, &mut |t| intersect_into(s, t, &PairSubset::Empty, f))
+    })
+}
+
+impl<T: PointMeasurable, U: PointMeasurable + ?Sized> PointMeasurable for (T, U) {
     #[with]
-    fn point_subset<'a>(&'a self) -> &'ref Self::Subset<'ref> {
+    fn point_subset<'a>(&'a self) -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
         let left: &'ref _ = T::point_subset(&self.0);
         let right: &'ref _ = U::point_subset(&self.1);
-        &PairSubset {
-            left: T::subset_upcast(left),
-            right: U::subset_upcast(right),
+        &PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(right))
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Passes the subset made up of the given owned rectangles to `f`.
+fn with_owned_rectangles<T: OwnedMeasurable, U: OwnedMeasurable + ?Sized>(
+    rectangles: &[(T::Owned, U::Owned)],
+    f: &mut PairFn<'_, T, U>,
+) {
+    match rectangles {
+        [] => f(&PairSubset::Empty),
+        [(left, right), rest @ ..] => with_owned_rectangles::<T, U>(rest, &mut |rest| {
+            T::with_subset_from_owned(left, |left| {
+                U::with_subset_from_owned(right, |right| {
+                    f(&PairSubset::Union {
+                        left: T::subset_upcast(left),
+                        right: U::subset_upcast(right),
+                        rest,
+                    })
+                })
+            })
+        }),
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// Subsets of pairs are owned as lists of disjoint rectangles, each side of
+/// which is owned by its own space. This allows an event to be built once and
+/// measured many times:
+/// ```
+/// use mes::{
+///     boolean::BoolMeasure,
+///     pair::{PairSubset, ProductMeasure},
+///     real::{dirac::Dirac, interval::Interval},
+///     Measurable, Measure, OwnedMeasurable, OwnedSubset, PointMeasurable,
+/// };
+///
+/// type Space = (f64, bool);
+///
+/// let near = Interval::closed(-1.0, 1.0);
+/// let event: OwnedSubset<Space> = true.with_point_subset(|b| {
+///     let s = PairSubset::<f64, bool>::rectangle(&near, bool::subset_upcast(b));
+///     OwnedSubset::<Space>::new(&s).unwrap()
+/// });
+/// let others = Space::subset_complement_owned(&event);
+/// assert_eq!(others.as_owned().len(), 2);
+///
+/// for point in [-2.0, 0.0, 0.5, 3.0] {
+///     let m = ProductMeasure {
+///         left: Dirac { point, weight: 1.0 },
+///         right: BoolMeasure {
+///             true_value: 0.25,
+///             false_value: 0.75,
+///         },
+///     };
+///     let inside = event.with_access(|s| m.with_measure(Space::subset_upcast(s), |x| *x));
+///     let outside = others.with_access(|s| m.with_measure(Space::subset_upcast(s), |x| *x));
+///     assert_eq!(inside, if point.abs() <= 1.0 { 0.25 } else { 0.0 });
+///     assert_eq!(inside + outside, 1.0);
+/// }
+/// ```
+impl<T: OwnedMeasurable, U: OwnedMeasurable + ?Sized> OwnedMeasurable for (T, U) {
+    type Owned = Vec<(T::Owned, U::Owned)>;
+
+    fn subset_to_owned(s: &(dyn DynPairSubset<T, U> + '_)) -> Option<Self::Owned> {
+        let mut rectangles = Vec::new();
+        let owned = s.all_rectangles(|left, right| {
+            match (T::subset_to_owned(left), U::subset_to_owned(right)) {
+                (Some(left), Some(right)) => {
+                    rectangles.push((left, right));
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        owned.then_some(rectangles)
+    }
+
+    #[allow(unused_macros)]
+    #[with(continuation_name = ret)]
+    fn subset_from_owned(s: &Self::Owned) -> &'ref (dyn DynPairSubset<T, U> + 'ref) {
+        let mut ret = Some(ret);
+        let mut result = None;
+        with_owned_rectangles::<T, U>(s, &mut |s| {
+            result = Some(ret.take().expect("continuation called multiple times!")(s))
+        });
+
+        result.expect("continuation was never called!")
+    }
+}
+
+impl<T: FiniteMeasurable, U: FiniteMeasurable> FiniteMeasurable for (T, U) {
+    const CARDINALITY: usize = T::CARDINALITY * U::CARDINALITY;
+
+    fn index(&self) -> usize {
+        self.0.index() * U::CARDINALITY + self.1.index()
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        if index < Self::CARDINALITY {
+            Some((
+                T::from_index(index / U::CARDINALITY)?,
+                U::from_index(index % U::CARDINALITY)?,
+            ))
+        } else {
+            None
         }
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Add, AddAssign, Mul, MulAssign)]
-/// A measure on [`bool`].
-pub struct BoolMeasure<R: Real> {
-    /// The value of the measure for `true`.
-    pub true_value: R,
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The product of a measure on `T` and a measure on `U`, as a measure on
+/// `(T, U)`.
+///
+/// The measure of a rectangle `A × B` is the product of the measures of `A`
+/// and `B`:
+/// ```
+/// use mes::{boolean::BoolMeasure, pair::ProductMeasure, Measurable, Measure, PointMeasurable};
+///
+/// let m = ProductMeasure {
+///     left: BoolMeasure {
+///         true_value: 0.25f64,
+///         false_value: 0.75f64,
+///     },
+///     right: BoolMeasure {
+///         true_value: 2.0f64,
+///         false_value: 4.0f64,
+///     },
+/// };
+///
+/// let x = (true, false)
+///     .with_point_subset(|p| m.with_measure(<(bool, bool)>::subset_upcast(p), |x| *x));
+/// assert_eq!(x, 1.0);
+/// ```
+pub struct ProductMeasure<M, N> {
+    /// The measure on the left component.
+    pub left: M,
 
-    /// The value of the measure for `false`.
-    pub false_value: R,
+    /// The measure on the right component.
+    pub right: N,
 }
 
-#[derive(Clone, Copy, PartialEq, PartialOrd)]
-/// A probability measure on [`bool`].
-pub struct BoolPMeasure<R: Real> {
-    /// The value of the probability measure for `true`.
-    true_value: R,
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The product of a probability measure on `T` and a probability measure on
+/// `U`, as a probability measure on `(T, U)`.
+pub struct ProductPMeasure<P, Q> {
+    /// The probability measure on the left component.
+    pub left: P,
+
+    /// The probability measure on the right component.
+    pub right: Q,
 }
 
-impl<R: Real> From<BoolPMeasure<R>> for BoolMeasure<R> {
-    fn from(m: BoolPMeasure<R>) -> Self {
+impl<M: From<P>, N: From<Q>, P, Q> From<ProductPMeasure<P, Q>> for ProductMeasure<M, N> {
+    fn from(m: ProductPMeasure<P, Q>) -> Self {
         Self {
-            true_value: m.true_value,
-            false_value: R::one() - m.true_value,
+            left: m.left.into(),
+            right: m.right.into(),
         }
     }
 }
 
-impl<'subset, R: Real> Measure<'subset> for BoolMeasure<R> {
-    type R = R;
+impl<M, N> ProductMeasure<M, N> {
+    /// Computes the marginal measure on the left component, which is `left`
+    /// scaled by the total mass of `right`.
+    ///
+    /// ```
+    /// use mes::{boolean::BoolMeasure, pair::ProductMeasure, real::dirac::Dirac};
+    ///
+    /// let joint = ProductMeasure {
+    ///     left: Dirac {
+    ///         point: 0.5f64,
+    ///         weight: 1.0,
+    ///     },
+    ///     right: BoolMeasure {
+    ///         true_value: 2.0,
+    ///         false_value: 4.0,
+    ///     },
+    /// };
+    ///
+    /// assert_eq!(joint.marginal_left().weight, 6.0);
+    /// assert_eq!(joint.marginal_right().true_value, 2.0);
+    /// ```
+    pub fn marginal_left<'subset>(&self) -> M
+    where
+        M: Measure<'subset> + Clone,
+        N: Measure<'subset, R = M::R>,
+        N::Measurement: Copy + Into<M::R>,
+    {
+        self.left.clone() * total_mass(&self.right)
+    }
 
-    type Space = bool;
+    /// Computes the marginal measure on the right component, which is `right`
+    /// scaled by the total mass of `left`.
+    pub fn marginal_right<'subset>(&self) -> N
+    where
+        M: Measure<'subset>,
+        N: Measure<'subset, R = M::R> + Clone,
+        M::Measurement: Copy + Into<M::R>,
+    {
+        self.right.clone() * total_mass(&self.left)
+    }
+}
 
-    type Measurement = R;
+/// Computes the measure of the full subset.
+fn total_mass<'subset, M: Measure<'subset>>(m: &M) -> M::R
+where
+    M::Measurement: Copy + Into<M::R>,
+{
+    M::Space::with_full_subset(|s| m.with_measure(M::Space::subset_upcast(s), |x| (*x).into()))
+}
 
-    type PMeasure = BoolPMeasure<R>;
+impl<R: Real, M: MulAssign<R>, N> Mul<R> for ProductMeasure<M, N> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        self.left *= rhs;
+        self
+    }
+}
+
+impl<R: Real, M: MulAssign<R>, N> MulAssign<R> for ProductMeasure<M, N> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.left *= rhs;
+    }
+}
+
+impl<'subset, M: Measure<'subset>, N: Measure<'subset, R = M::R>> Measure<'subset>
+    for ProductMeasure<M, N>
+where
+    M::Space: Sized,
+    M::Measurement: Copy + Into<M::R>,
+    N::Measurement: Copy + Into<M::R>,
+{
+    type R = M::R;
+
+    type Space = (M::Space, N::Space);
+
+    type Measurement = M::R;
+
+    type PMeasure = ProductPMeasure<M::PMeasure, N::PMeasure>;
 
     #[with]
     fn measure<'a>(
@@ -278,48 +890,544 @@ impl<'subset, R: Real> Measure<'subset> for BoolMeasure<R> {
     where
         'subset: 'a,
     {
-        let mut result = R::zero();
-        if domain.includes_true {
-            result += self.true_value;
+        let mut result = M::R::zero();
+        domain.for_each_rectangle(|left, right| {
+            let left = self
+                .left
+                .with_measure(M::Space::subset_upcast(left), |x| (*x).into());
+            let right = self
+                .right
+                .with_measure(N::Space::subset_upcast(right), |x| (*x).into());
+            result += left * right;
+        });
+
+        &result
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        Some(ProductPMeasure {
+            left: self.left.normalize()?,
+            right: self.right.normalize()?,
+        })
+    }
+}
+
+impl<'subset, M: PointMeasure<'subset>, N: PointMeasure<'subset, R = M::R>> PointMeasure<'subset>
+    for ProductMeasure<M, N>
+where
+    M::Space: Sized,
+    M::Measurement: Copy + Into<M::R>,
+    N::Measurement: Copy + Into<M::R>,
+    M::PointMeasurement: Copy + Into<M::R>,
+    N::PointMeasurement: Copy + Into<M::R>,
+{
+    type PointMeasurement = M::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        let left = self.left.with_measure_at(&value.0, |x| (*x).into());
+        let right = self.right.with_measure_at(&value.1, |x| (*x).into());
+        &(left * right)
+    }
+}
+
+impl<'subset, M: DiracMeasure<'subset>, N: DiracMeasure<'subset, R = M::R>> DiracMeasure<'subset>
+    for ProductMeasure<M, N>
+where
+    M::Space: Sized,
+    M::Measurement: Copy + Into<M::R>,
+    N::Measurement: Copy + Into<M::R>,
+{
+    fn dirac(point: &Self::Space) -> Self {
+        Self {
+            left: M::dirac(&point.0),
+            right: N::dirac(&point.1),
         }
-        if domain.includes_false {
-            result += self.false_value;
+    }
+}
+
+impl<'subset, M: IntegrableMeasure<'subset>, N: IntegrableMeasure<'subset, R = M::R>>
+    IntegrableMeasure<'subset> for ProductMeasure<M, N>
+where
+    M::Space: Sized + Clone,
+    N::Space: Sized + Clone,
+    M::Measurement: Copy + Into<M::R>,
+    N::Measurement: Copy + Into<M::R>,
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        self.left
+            .integrate(|x| self.right.integrate(|y| f(&(x.clone(), y.clone()))))
+    }
+}
+
+/// Describes a measure on `(T, U)`, from which the marginal measures on finite
+/// components can be computed.
+///
+/// This is implemented for every measure on a pair space whose measurements
+/// are real numbers, but only for components which are [`PointMeasurable`],
+/// since the marginals are built point by point as any [`FiniteMeasure`] on the
+/// respective component:
+/// ```
+/// use mes::{
+///     boolean::BoolMeasure,
+///     pair::{JointMeasure, ProductMeasure},
+///     ArrayMixture, PointMeasure,
+/// };
+///
+/// let same = |x: bool| BoolMeasure {
+///     true_value: if x { 1.0f64 } else { 0.0 },
+///     false_value: if x { 0.0 } else { 1.0 },
+/// };
+/// let mut joint = ArrayMixture::<_, 2>::new();
+/// assert!(joint
+///     .push(ProductMeasure {
+///         left: same(true) * 0.25,
+///         right: same(true),
+///     })
+///     .is_ok());
+/// assert!(joint
+///     .push(ProductMeasure {
+///         left: same(false) * 0.75,
+///         right: same(false),
+///     })
+///     .is_ok());
+///
+/// let left: BoolMeasure<f64> = joint.marginal_left();
+/// assert_eq!(left.with_measure_at(&true, |x| *x), 0.25);
+///
+/// let right: BoolMeasure<f64> = joint.marginal_right();
+/// assert_eq!(right.with_measure_at(&false, |x| *x), 0.75);
+/// ```
+///
+/// Measures with closed-form marginals provide them as inherent methods
+/// instead, which also work on components that are not finite, such as
+/// [`ProductMeasure::marginal_left`] and the marginals of bivariate Gaussians.
+pub trait JointMeasure<'subset, T: Measurable, U: Measurable + ?Sized>:
+    Measure<'subset, Space = (T, U)>
+{
+    /// Computes the marginal measure on `T`, i.e., the pushforward of the
+    /// measure along [`LeftProjection`].
+    fn marginal_left<M: FiniteMeasure<'subset, Space = T, R = Self::R>>(&self) -> M
+    where
+        T: PointMeasurable;
+
+    /// Computes the marginal measure on `U`, i.e., the pushforward of the
+    /// measure along [`RightProjection`].
+    fn marginal_right<N: FiniteMeasure<'subset, Space = U, R = Self::R>>(&self) -> N
+    where
+        U: PointMeasurable;
+}
+
+impl<'subset, T: Measurable, U: Measurable + ?Sized, J: Measure<'subset, Space = (T, U)>>
+    JointMeasure<'subset, T, U> for J
+where
+    J::Measurement: Copy + Into<J::R>,
+{
+    fn marginal_left<M: FiniteMeasure<'subset, Space = T, R = Self::R>>(&self) -> M
+    where
+        T: PointMeasurable,
+    {
+        M::from_point_masses(|x| {
+            x.with_point_subset(|left| {
+                U::with_full_subset(|right| {
+                    let s = PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(right));
+                    self.with_measure(&s, |m| (*m).into())
+                })
+            })
+        })
+    }
+
+    fn marginal_right<N: FiniteMeasure<'subset, Space = U, R = Self::R>>(&self) -> N
+    where
+        U: PointMeasurable,
+    {
+        N::from_point_masses(|x| {
+            x.with_point_subset(|right| {
+                T::with_full_subset(|left| {
+                    let s = PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(right));
+                    self.with_measure(&s, |m| (*m).into())
+                })
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The joint measure on `(T, U)` of a measure on `T` and a kernel from `T` to
+/// measures on `U`, i.e., the semi-direct product of the two.
+///
+/// This is the measure of the result of sampling `x` from `marginal` and then
+/// `y` from `kernel.apply(x)`. Its right marginal is
+/// [`bind(marginal, kernel)`](crate::bind), and if the kernel gives probability
+/// measures then its left marginal is `marginal`. It is evaluated
+/// exactly whenever `marginal` is an [`IntegrableMeasure`], which includes any
+/// boxed [`DynMeasure`](crate::DynMeasure) on a finite space:
+/// ```
+/// use mes::{
+///     boolean::BoolMeasure,
+///     kernel,
+///     pair::{JointMeasure, SemidirectProduct},
+///     PointMeasure,
+/// };
+///
+/// let rain = BoolMeasure {
+///     true_value: 0.25f64,
+///     false_value: 0.75f64,
+/// };
+/// let wet_grass = kernel(|rain: &bool| {
+///     if *rain {
+///         BoolMeasure {
+///             true_value: 0.9,
+///             false_value: 0.1,
+///         }
+///     } else {
+///         BoolMeasure {
+///             true_value: 0.2,
+///             false_value: 0.8,
+///         }
+///     }
+/// });
+///
+/// let joint = SemidirectProduct {
+///     marginal: rain,
+///     kernel: wet_grass,
+/// };
+/// assert_eq!(joint.with_measure_at(&(true, true), |x| *x), 0.225);
+///
+/// let wet: BoolMeasure<f64> = joint.marginal_right();
+/// assert_eq!(wet.true_value, 0.375);
+/// ```
+pub struct SemidirectProduct<M, K> {
+    /// The measure on the left component.
+    pub marginal: M,
+
+    /// The kernel giving the measure on the right component.
+    pub kernel: K,
+}
+
+impl<R, M: Mul<R, Output = M>, K> Mul<R> for SemidirectProduct<M, K> {
+    type Output = Self;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Self {
+            marginal: self.marginal * rhs,
+            kernel: self.kernel,
         }
-        &result
+    }
+}
+
+impl<R, M: MulAssign<R>, K> MulAssign<R> for SemidirectProduct<M, K> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.marginal *= rhs;
+    }
+}
+
+impl<'subset, M: IntegrableMeasure<'subset>, K: Kernel<'subset, Domain = M::Space> + Clone>
+    Measure<'subset> for SemidirectProduct<M, K>
+where
+    M::Space: PointMeasurable + Sized,
+    K::Measure: Measure<'subset, R = M::R>,
+    <K::Measure as Measure<'subset>>::Measurement: Copy + Into<M::R>,
+{
+    type R = M::R;
+
+    type Space = (M::Space, <K::Measure as Measure<'subset>>::Space);
+
+    type Measurement = M::R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &self.marginal.integrate(|x| {
+            let m = self.kernel.apply(x);
+            let mut result = M::R::zero();
+            x.with_point_subset(|p| {
+                domain.for_each_rectangle(|left, right| {
+                    if M::Space::subset_includes(left, p) {
+                        result += m.with_measure(
+                            <K::Measure as Measure<'subset>>::Space::subset_upcast(right),
+                            |y| (*y).into(),
+                        );
+                    }
+                })
+            });
+            result
+        })
     }
 
     fn normalize(&self) -> Option<Self::PMeasure> {
-        Some(BoolPMeasure {
-            true_value: R::normalize_static([self.true_value, self.false_value])?[0],
+        // Normalizing the marginal first avoids having to clone it.
+        let marginal = M::from(self.marginal.normalize()?);
+        let total = marginal.integrate(|x| {
+            let m = self.kernel.apply(x);
+            <K::Measure as Measure<'subset>>::Space::with_full_subset(|s| {
+                m.with_measure(
+                    <K::Measure as Measure<'subset>>::Space::subset_upcast(s),
+                    |y| (*y).into(),
+                )
+            })
+        });
+        let factor = total.recip();
+        if !factor.is_finite() {
+            return None;
+        }
+
+        Some(Self {
+            marginal: marginal * factor,
+            kernel: self.kernel.clone(),
         })
     }
 }
 
-impl<'subset, R: Real> PointMeasure<'subset> for BoolMeasure<R> {
-    type PointMeasurement = R;
+impl<'subset, M: IntegrableMeasure<'subset>, K: Kernel<'subset, Domain = M::Space> + Clone>
+    PointMeasure<'subset> for SemidirectProduct<M, K>
+where
+    M: PointMeasure<'subset>,
+    M::Space: PointMeasurable + Sized,
+    M::PointMeasurement: Copy + Into<M::R>,
+    K::Measure: PointMeasure<'subset, R = M::R>,
+    <K::Measure as Measure<'subset>>::Measurement: Copy + Into<M::R>,
+    <K::Measure as PointMeasure<'subset>>::PointMeasurement: Copy + Into<M::R>,
+{
+    type PointMeasurement = M::R;
 
     #[with]
     fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
-        if *value {
-            &self.true_value
-        } else {
-            &self.false_value
+        let left = self.marginal.with_measure_at(&value.0, |x| (*x).into());
+        let right = self
+            .kernel
+            .apply(&value.0)
+            .with_measure_at(&value.1, |y| (*y).into());
+        &(left * right)
+    }
+}
+
+impl<'subset, M: IntegrableMeasure<'subset>, K: Kernel<'subset, Domain = M::Space> + Clone>
+    IntegrableMeasure<'subset> for SemidirectProduct<M, K>
+where
+    M::Space: PointMeasurable + Clone,
+    K::Measure: IntegrableMeasure<'subset, R = M::R>,
+    <K::Measure as Measure<'subset>>::Space: Clone,
+    <K::Measure as Measure<'subset>>::Measurement: Copy + Into<M::R>,
+{
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        self.marginal.integrate(|x| {
+            self.kernel
+                .apply(x)
+                .integrate(|y| f(&(x.clone(), y.clone())))
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+/// The kernel giving the regular conditional measures on `U` of a joint measure
+/// on `(T, U)` given the left component, i.e., its disintegration.
+///
+/// Constructed by [`disintegrate`]. The kernel borrows the joint measure, as do
+/// the measures it produces, so that applying it never copies the joint
+/// measure.
+pub struct Disintegration<'j, J> {
+    joint: &'j J,
+}
+
+impl<'j, J> Clone for Disintegration<'j, J> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'j, J> Copy for Disintegration<'j, J> {}
+
+impl<'j, J> Disintegration<'j, J> {
+    /// The joint measure being disintegrated.
+    pub fn joint(&self) -> &'j J {
+        self.joint
+    }
+}
+
+impl<'subset, 'j, T: FiniteMeasurable + Clone, U: Measurable + ?Sized, J> Kernel<'subset>
+    for Disintegration<'j, J>
+where
+    J: Measure<'subset, Space = (T, U)>,
+    J::Measurement: Copy + Into<J::R>,
+{
+    type Domain = T;
+
+    type Measure = Slice<'j, J, T, J::R>;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        let factor = slice_mass(self.joint, x).recip();
+        Slice {
+            joint: self.joint,
+            point: x.clone(),
+            factor: if factor.is_finite() {
+                factor
+            } else {
+                J::R::zero()
+            },
         }
     }
 }
 
-impl<'subset, R: Real> DiracMeasure<'subset> for BoolMeasure<R> {
-    fn dirac(point: &Self::Space) -> Self {
-        if *point {
-            Self {
-                true_value: R::one(),
-                false_value: R::zero(),
-            }
-        } else {
-            Self {
-                true_value: R::zero(),
-                false_value: R::one(),
-            }
+/// Disintegrates a joint measure on `(T, U)` along its left component.
+///
+/// Applying the resulting kernel to `x` gives the conditional probability
+/// measure on `U` given that the left component is `x`. Where `x` has zero
+/// measure, this is the zero measure instead.
+///
+/// This undoes [`SemidirectProduct`]:
+/// ```
+/// use mes::{
+///     boolean::BoolMeasure,
+///     kernel,
+///     pair::{disintegrate, SemidirectProduct},
+///     Kernel, PointMeasure,
+/// };
+///
+/// let joint = SemidirectProduct {
+///     marginal: BoolMeasure {
+///         true_value: 0.25f64,
+///         false_value: 0.75f64,
+///     },
+///     kernel: kernel(|x: &bool| BoolMeasure {
+///         true_value: if *x { 0.5 } else { 1.0 },
+///         false_value: 0.5,
+///     }),
+/// };
+///
+/// let posterior = disintegrate(&joint);
+/// assert_eq!(posterior.apply(&true).with_measure_at(&true, |x| *x), 0.5);
+///
+/// let given_false = posterior.apply(&false);
+/// assert_eq!(given_false.with_measure_at(&false, |x| *x), 1.0 / 3.0);
+/// ```
+pub fn disintegrate<'subset, T: FiniteMeasurable, U: Measurable + ?Sized, J>(
+    joint: &J,
+) -> Disintegration<'_, J>
+where
+    J: Measure<'subset, Space = (T, U)>,
+{
+    Disintegration { joint }
+}
+
+/// Computes the measure of `{x} × U` under the given joint measure.
+fn slice_mass<'subset, T: PointMeasurable, U: Measurable + ?Sized, J>(joint: &J, x: &T) -> J::R
+where
+    J: Measure<'subset, Space = (T, U)>,
+    J::Measurement: Copy + Into<J::R>,
+{
+    x.with_point_subset(|left| {
+        U::with_full_subset(|right| {
+            let s = PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(right));
+            joint.with_measure(&s, |m| (*m).into())
+        })
+    })
+}
+
+#[derive(Debug, PartialEq)]
+/// The measure on `U` obtained by restricting a joint measure on `(T, U)` to
+/// the slice `{point} × U`, scaled by a factor.
+///
+/// Produced by applying a [`Disintegration`].
+pub struct Slice<'j, J, T, R> {
+    joint: &'j J,
+
+    /// The point of `T` at which the joint measure is sliced.
+    pub point: T,
+
+    /// The factor by which the joint measure is scaled.
+    pub factor: R,
+}
+
+impl<'j, J, T: Clone, R: Clone> Clone for Slice<'j, J, T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            joint: self.joint,
+            point: self.point.clone(),
+            factor: self.factor.clone(),
+        }
+    }
+}
+
+impl<'j, J, T: Copy, R: Copy> Copy for Slice<'j, J, T, R> {}
+
+impl<'j, J, T, R: Real> Mul<R> for Slice<'j, J, T, R> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: R) -> Self::Output {
+        self.factor *= rhs;
+        self
+    }
+}
+
+impl<'j, J, T, R: Real> MulAssign<R> for Slice<'j, J, T, R> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.factor *= rhs;
+    }
+}
+
+impl<'subset, 'j, T: PointMeasurable + Clone, U: Measurable + ?Sized, J> Measure<'subset>
+    for Slice<'j, J, T, J::R>
+where
+    J: Measure<'subset, Space = (T, U)>,
+    J::Measurement: Copy + Into<J::R>,
+{
+    type R = J::R;
+
+    type Space = U;
+
+    type Measurement = J::R;
+
+    type PMeasure = Self;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &(self.factor
+            * self.point.with_point_subset(|left| {
+                let s = PairSubset::rectangle(T::subset_upcast(left), U::subset_upcast(domain));
+                self.joint.with_measure(&s, |m| (*m).into())
+            }))
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        let factor = slice_mass(self.joint, &self.point).recip();
+        if !factor.is_finite() {
+            return None;
         }
+
+        Some(Self {
+            factor,
+            ..self.clone()
+        })
+    }
+}
+
+impl<'subset, 'j, T: PointMeasurable + Clone, U: Measurable, J> PointMeasure<'subset>
+    for Slice<'j, J, T, J::R>
+where
+    J: PointMeasure<'subset, Space = (T, U)>,
+    J::Measurement: Copy + Into<J::R>,
+    J::PointMeasurement: Copy + Into<J::R>,
+    U: Clone,
+{
+    type PointMeasurement = J::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        let point = (self.point.clone(), value.clone());
+        &(self.factor * self.joint.with_measure_at(&point, |m| (*m).into()))
     }
 }
@@ -0,0 +1,569 @@
+//! Implementation of bivariate Gaussian distributions on pairs of real numbers.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use core::ops::{Mul, MulAssign};
+use num_traits::{Float, FloatConst};
+use with_locals::with;
+
+use crate::{special::erfc, Kernel, Measurable, Measure, PointMeasure};
+
+use super::{
+    gaussian::{Gaussian, PGaussian},
+    interval::Interval,
+    Real, RealSubset,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A weighted bivariate Gaussian measure on pairs of real numbers.
+///
+/// Subsets are measured through the bivariate normal CDF, provided that the
+/// sides of their rectangles can be decomposed into intervals. For instance,
+/// the quadrant above the mean has probability `1/4 + asin(ρ) / 2π`:
+/// ```
+/// use mes::{
+///     pair::PairSubset,
+///     real::{
+///         bivariate_gaussian::{BivariateGaussian, PBivariateGaussian},
+///         gaussian::PGaussian,
+///         interval::Interval,
+///     },
+///     Measure, PointMeasure,
+/// };
+///
+/// let m = BivariateGaussian {
+///     distribution: PBivariateGaussian {
+///         left: PGaussian {
+///             mean: 1.0f64,
+///             variance: 4.0,
+///         },
+///         right: PGaussian {
+///             mean: -1.0,
+///             variance: 0.25,
+///         },
+///         correlation: 0.5,
+///     },
+///     weight: 2.0,
+/// };
+///
+/// let left = Interval::new(1.0, f64::INFINITY, false, true);
+/// let right = Interval::new(-1.0, f64::INFINITY, false, true);
+/// let x = m.with_measure(&PairSubset::<f64, f64>::rectangle(&left, &right), |x| *x);
+/// assert!((x - 2.0 * (0.25 + 0.5f64.asin() / std::f64::consts::TAU)).abs() < 1e-12);
+///
+/// // The density at the mean is `1 / (2π σ₁ σ₂ sqrt(1 - ρ²))`.
+/// let y = m.with_measure_at(&(1.0, -1.0), |x| *x);
+/// assert!((y - 2.0 / (std::f64::consts::TAU * 0.75f64.sqrt())).abs() < 1e-12);
+/// ```
+pub struct BivariateGaussian<R: Real> {
+    /// The distribution of the measure.
+    pub distribution: PBivariateGaussian<R>,
+
+    /// The weight of the measure.
+    pub weight: R,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A bivariate Gaussian distribution on pairs of real numbers, given by its
+/// marginal distributions and the correlation between them.
+pub struct PBivariateGaussian<R: Real> {
+    /// The distribution of the left component.
+    pub left: PGaussian<R>,
+
+    /// The distribution of the right component.
+    pub right: PGaussian<R>,
+
+    /// The correlation between the components, between -1 and 1.
+    pub correlation: R,
+}
+
+impl<R: Real> BivariateGaussian<R> {
+    /// Computes the marginal measure on the left component.
+    ///
+    /// ```
+    /// use mes::real::{
+    ///     bivariate_gaussian::{BivariateGaussian, PBivariateGaussian},
+    ///     gaussian::PGaussian,
+    /// };
+    ///
+    /// let left = PGaussian {
+    ///     mean: 1.0f64,
+    ///     variance: 4.0,
+    /// };
+    /// let right = PGaussian {
+    ///     mean: -1.0,
+    ///     variance: 0.25,
+    /// };
+    /// let m = BivariateGaussian {
+    ///     distribution: PBivariateGaussian {
+    ///         left,
+    ///         right,
+    ///         correlation: 0.5,
+    ///     },
+    ///     weight: 2.0,
+    /// };
+    ///
+    /// assert_eq!(m.marginal_left().distribution, left);
+    /// assert_eq!(m.marginal_right().distribution, right);
+    /// assert_eq!(m.marginal_right().weight, 2.0);
+    /// ```
+    pub fn marginal_left(&self) -> Gaussian<R> {
+        Gaussian {
+            distribution: self.distribution.left,
+            weight: self.weight,
+        }
+    }
+
+    /// Computes the marginal measure on the right component.
+    pub fn marginal_right(&self) -> Gaussian<R> {
+        Gaussian {
+            distribution: self.distribution.right,
+            weight: self.weight,
+        }
+    }
+}
+
+impl<R: Real + Float + FloatConst> BivariateGaussian<R> {
+    /// Disintegrates the measure along its left component.
+    ///
+    /// Applying the resulting kernel to `x` gives the conditional probability
+    /// measure of the right component given that the left component is `x`,
+    /// which agrees with the measure of thin strips around `x`:
+    /// ```
+    /// use mes::{
+    ///     pair::PairSubset,
+    ///     real::{
+    ///         bivariate_gaussian::{BivariateGaussian, PBivariateGaussian},
+    ///         gaussian::{Gaussian, PGaussian},
+    ///         interval::Interval,
+    ///     },
+    ///     Kernel, Measure,
+    /// };
+    ///
+    /// let m = BivariateGaussian {
+    ///     distribution: PBivariateGaussian {
+    ///         left: PGaussian {
+    ///             mean: 0.0f64,
+    ///             variance: 4.0,
+    ///         },
+    ///         right: PGaussian {
+    ///             mean: 1.0,
+    ///             variance: 9.0,
+    ///         },
+    ///         correlation: 0.5,
+    ///     },
+    ///     weight: 1.0,
+    /// };
+    ///
+    /// let posterior = m.disintegrate();
+    /// let given = posterior.apply(&2.0);
+    /// assert_eq!(given.distribution.mean, 2.5);
+    /// assert_eq!(given.distribution.variance, 6.75);
+    ///
+    /// let strip = Interval::closed(2.0 - 1e-4, 2.0 + 1e-4);
+    /// let above = Interval::new(4.0, f64::INFINITY, false, true);
+    /// let joint = m.with_measure(&PairSubset::<f64, f64>::rectangle(&strip, &above), |x| *x);
+    /// let marginal = Gaussian::from(m.distribution.left).with_measure(&strip, |x| *x);
+    /// let conditional = given.with_measure(&above, |x| *x);
+    /// assert!((joint / marginal - conditional).abs() < 1e-6);
+    /// ```
+    pub fn disintegrate(&self) -> LinearGaussianKernel<R> {
+        self.distribution.disintegrate()
+    }
+}
+
+impl<R: Real + Float + FloatConst> PBivariateGaussian<R> {
+    /// The density of the distribution at the given point.
+    ///
+    /// Where the distribution is concentrated on a point or a line, this is
+    /// infinite there, as for a Dirac measure.
+    pub fn density(&self, (x, y): (R, R)) -> R {
+        let (sx, sy) = (self.left.variance.sqrt(), self.right.variance.sqrt());
+        let rest = R::one() - self.correlation * self.correlation;
+        if sx.is_zero() || sy.is_zero() {
+            // The distribution is concentrated on a vertical or horizontal line,
+            // or on a point if both variances are zero.
+            let on_left = !sx.is_zero() || x == self.left.mean;
+            let on_right = !sy.is_zero() || y == self.right.mean;
+            return if on_left && on_right {
+                <R as Float>::infinity()
+            } else {
+                R::zero()
+            };
+        }
+
+        let (u, v) = ((x - self.left.mean) / sx, (y - self.right.mean) / sy);
+        if rest.is_zero() {
+            return if v == self.correlation * u {
+                <R as Float>::infinity()
+            } else {
+                R::zero()
+            };
+        }
+
+        let q = (u * u - (self.correlation + self.correlation) * u * v + v * v) / rest;
+        (-q / (R::one() + R::one())).exp() / (R::TAU() * sx * sy * rest.sqrt())
+    }
+
+    /// Disintegrates the distribution along its left component.
+    ///
+    /// Applying the resulting kernel to `x` gives the conditional distribution
+    /// of the right component given that the left component is `x`. See
+    /// [`BivariateGaussian::disintegrate`].
+    ///
+    /// With a constant left component the components are independent, so the
+    /// conditional distribution is the right marginal, whatever the
+    /// correlation:
+    /// ```
+    /// use mes::{
+    ///     real::{bivariate_gaussian::PBivariateGaussian, gaussian::PGaussian},
+    ///     Kernel,
+    /// };
+    ///
+    /// let d = PBivariateGaussian {
+    ///     left: PGaussian {
+    ///         mean: 1.0f64,
+    ///         variance: 0.0,
+    ///     },
+    ///     right: PGaussian {
+    ///         mean: -2.0,
+    ///         variance: 3.0,
+    ///     },
+    ///     correlation: 0.5,
+    /// };
+    ///
+    /// let given = d.disintegrate().apply(&1.0);
+    /// assert_eq!(given.distribution, d.right);
+    /// ```
+    pub fn disintegrate(&self) -> LinearGaussianKernel<R> {
+        if self.left.variance.is_zero() {
+            return LinearGaussianKernel {
+                slope: R::zero(),
+                intercept: self.right.mean,
+                variance: self.right.variance,
+            };
+        }
+
+        let slope = self.correlation * (self.right.variance / self.left.variance).sqrt();
+        LinearGaussianKernel {
+            slope,
+            intercept: self.right.mean - slope * self.left.mean,
+            variance: self.right.variance * (R::one() - self.correlation * self.correlation),
+        }
+    }
+
+    /// The probability of the rectangle `left × right`.
+    fn rectangle_probability(
+        &self,
+        left: &(dyn RealSubset<R> + '_),
+        right: &(dyn RealSubset<R> + '_),
+    ) -> R {
+        // With a constant component, the components are independent.
+        if self.left.variance.is_zero() {
+            let x = if left.contains(&self.left.mean) {
+                R::one()
+            } else {
+                R::zero()
+            };
+            return x * self.right.probability(right);
+        } else if self.right.variance.is_zero() {
+            let y = if right.contains(&self.right.mean) {
+                R::one()
+            } else {
+                R::zero()
+            };
+            return self.left.probability(left) * y;
+        }
+
+        let (sx, sy) = (self.left.variance.sqrt(), self.right.variance.sqrt());
+        let standardize_left = |x| (x - self.left.mean) / sx;
+        let standardize_right = |y| (y - self.right.mean) / sy;
+        let upper = |h, k| upper_orthant(h, k, self.correlation);
+
+        let mut sum = R::zero();
+        let mut right_decomposed = true;
+        let left_decomposed = for_each_interval(left, |i| {
+            let (h1, h2) = (standardize_left(i.lower), standardize_left(i.upper));
+            right_decomposed &= for_each_interval(right, |j| {
+                let (k1, k2) = (standardize_right(j.lower), standardize_right(j.upper));
+                let p = upper(h1, k1) - upper(h2, k1) - upper(h1, k2) + upper(h2, k2);
+                if p > R::zero() {
+                    sum += p;
+                }
+            });
+        });
+
+        if left_decomposed && right_decomposed {
+            sum
+        } else {
+            <R as Float>::nan()
+        }
+    }
+}
+
+impl<R: Real> From<PBivariateGaussian<R>> for BivariateGaussian<R> {
+    fn from(distribution: PBivariateGaussian<R>) -> Self {
+        Self {
+            distribution,
+            weight: R::one(),
+        }
+    }
+}
+
+impl<R: Real> Mul<R> for BivariateGaussian<R> {
+    type Output = Self;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        Self {
+            distribution: self.distribution,
+            weight: self.weight * rhs,
+        }
+    }
+}
+
+impl<R: Real> MulAssign<R> for BivariateGaussian<R> {
+    fn mul_assign(&mut self, rhs: R) {
+        self.weight *= rhs;
+    }
+}
+
+impl<'subset, R: Real + Float + FloatConst> Measure<'subset> for BivariateGaussian<R> {
+    type R = R;
+
+    type Space = (R, R);
+
+    type Measurement = R;
+
+    type PMeasure = PBivariateGaussian<R>;
+
+    #[with]
+    /// Computes the measure of the given subset.
+    ///
+    /// The measurement is NaN if a side of one of the rectangles making up the
+    /// subset is neither empty nor full and cannot be decomposed into
+    /// intervals, unless the variance of that side is zero.
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        let mut sum = R::zero();
+        domain.for_each_rectangle(|left, right| {
+            sum += self.distribution.rectangle_probability(left, right)
+        });
+        &(self.weight * sum)
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        R::normalize_static([self.weight])?;
+        Some(self.distribution)
+    }
+}
+
+impl<'subset, R: Real + Float + FloatConst> PointMeasure<'subset> for BivariateGaussian<R> {
+    type PointMeasurement = R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &(self.weight * self.distribution.density(*value))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The kernel taking `x` to the Gaussian distribution with mean
+/// `slope * x + intercept` and the given variance.
+pub struct LinearGaussianKernel<R: Real> {
+    /// The dependence of the mean on the point.
+    pub slope: R,
+
+    /// The mean at zero.
+    pub intercept: R,
+
+    /// The variance of the distributions.
+    pub variance: R,
+}
+
+impl<'subset, R: Real + Float + FloatConst> Kernel<'subset> for LinearGaussianKernel<R> {
+    type Domain = R;
+
+    type Measure = Gaussian<R>;
+
+    fn apply(&self, x: &Self::Domain) -> Self::Measure {
+        PGaussian {
+            mean: self.slope * *x + self.intercept,
+            variance: self.variance,
+        }
+        .into()
+    }
+}
+
+/// Calls `f` on each of the intervals making up `s`, or returns `false` if `s`
+/// is neither empty nor full and cannot be decomposed into intervals.
+fn for_each_interval<R: Real>(
+    s: &(dyn RealSubset<R> + '_),
+    mut f: impl FnMut(Interval<R>),
+) -> bool {
+    match s.intervals() {
+        Some(intervals) => intervals.for_each(f),
+        None if s.is_empty() => {}
+        None if s.is_full() => f(Interval::full()),
+        None => return false,
+    }
+
+    true
+}
+
+/// Converts a constant into `R`.
+fn constant<R: Float>(x: f64) -> R {
+    R::from(x).unwrap()
+}
+
+/// The standard normal CDF.
+fn normal_cdf<R: Float + FloatConst>(x: R) -> R {
+    erfc(-x * R::FRAC_1_SQRT_2()) / (R::one() + R::one())
+}
+
+/// The positive halves of the 6-, 12- and 20-point Gauss-Legendre rules on
+/// `[-1, 1]`, as pairs of abscissae and weights.
+const GAUSS_LEGENDRE: [&[(f64, f64)]; 3] = [
+    &[
+        (0.9324695142031522, 0.1713244923791705),
+        (0.6612093864662647, 0.3607615730481384),
+        (0.238619186083197, 0.4679139345726904),
+    ],
+    &[
+        (0.9815606342467191, 0.04717533638651177),
+        (0.904117256370475, 0.1069393259953183),
+        (0.769902674194305, 0.1600783285433464),
+        (0.5873179542866171, 0.2031674267230659),
+        (0.3678314989981802, 0.2334925365383547),
+        (0.1252334085114692, 0.2491470458134029),
+    ],
+    &[
+        (0.9931285991850949, 0.01761400713915212),
+        (0.9639719272779138, 0.04060142980038694),
+        (0.912234428251326, 0.06267204833410905),
+        (0.8391169718222188, 0.08327674157670475),
+        (0.7463319064601508, 0.1019301198172404),
+        (0.636053680726515, 0.1181945319615184),
+        (0.5108670019508271, 0.1316886384491766),
+        (0.3737060887154196, 0.1420961093183821),
+        (0.2277858511416451, 0.1491729864726037),
+        (0.07652652113349732, 0.1527533871307259),
+    ],
+];
+
+/// The probability that a standard bivariate normal with correlation `r`
+/// exceeds `h` in its first component and `k` in its second.
+///
+/// This follows A. Genz, "Numerical computation of rectangular bivariate and
+/// trivariate normal and t probabilities", Statistics and Computing 14 (2004),
+/// which integrates over the correlation by Gauss-Legendre quadrature, to
+/// around `1e-15` absolute error in `f64`.
+fn upper_orthant<R: Float + FloatConst>(h: R, k: R, r: R) -> R {
+    let (zero, one) = (R::zero(), R::one());
+    let two = one + one;
+    if h == R::infinity() || k == R::infinity() {
+        return zero;
+    } else if h == R::neg_infinity() {
+        return if k == R::neg_infinity() {
+            one
+        } else {
+            normal_cdf(-k)
+        };
+    } else if k == R::neg_infinity() {
+        return normal_cdf(-h);
+    }
+
+    let rule = if r.abs() < constant(0.3) {
+        GAUSS_LEGENDRE[0]
+    } else if r.abs() < constant(0.75) {
+        GAUSS_LEGENDRE[1]
+    } else {
+        GAUSS_LEGENDRE[2]
+    };
+    // Each abscissa `x` of the rule stands for the points `1 - x` and `1 + x`
+    // of the same rule shifted to `[0, 2]`.
+    let points = || {
+        rule.iter().flat_map(|&(x, w)| {
+            let (x, w) = (constant::<R>(x), constant::<R>(w));
+            [(R::one() - x, w), (R::one() + x, w)]
+        })
+    };
+
+    let mut hk = h * k;
+    let bvn = if r.abs() < constant(0.925) {
+        // Integrate the density of the correlation from 0 to `r`, through the
+        // substitution `r = sin(θ)`.
+        let hs = (h * h + k * k) / two;
+        let asr = r.asin() / two;
+        let sum = points().fold(zero, |sum, (x, w)| {
+            let sn = (asr * x).sin();
+            sum + w * ((sn * hk - hs) / (one - sn * sn)).exp()
+        });
+        sum * asr / R::TAU() + normal_cdf(-h) * normal_cdf(-k)
+    } else {
+        // Integrate from `|r|` to 1 instead, subtracting the singularity at 1.
+        let k = if r < zero {
+            hk = -hk;
+            -k
+        } else {
+            k
+        };
+        let mut bvn = zero;
+        if r.abs() < one {
+            let a2 = one - r * r;
+            let a = a2.sqrt();
+            let b2 = (h - k) * (h - k);
+            let c = (constant::<R>(4.0) - hk) / constant(8.0);
+            let d = (constant::<R>(12.0) - hk) / constant(80.0);
+            let cutoff = constant::<R>(-100.0);
+
+            let asr = -(b2 / a2 + hk) / two;
+            if asr > cutoff {
+                bvn = a
+                    * asr.exp()
+                    * (one - c * (b2 - a2) * (one - d * b2) / constant(3.0) + c * d * a2 * a2);
+            }
+            if hk > cutoff {
+                let b = b2.sqrt();
+                let sp = R::TAU().sqrt() * normal_cdf(-b / a);
+                bvn = bvn
+                    - (-hk / two).exp() * sp * b * (one - c * b2 * (one - d * b2) / constant(3.0));
+            }
+
+            let a = a / two;
+            let sum = points().fold(zero, |sum, (x, w)| {
+                let xs = (a * x) * (a * x);
+                let asr = -(b2 / xs + hk) / two;
+                if asr > cutoff {
+                    let sp = one + c * xs * (one + constant::<R>(5.0) * d * xs);
+                    let rs = (one - xs).sqrt();
+                    let ep = (-(hk / two) * xs / ((one + rs) * (one + rs))).exp() / rs;
+                    sum + w * asr.exp() * (sp - ep)
+                } else {
+                    sum
+                }
+            });
+            bvn = (a * sum - bvn) / R::TAU();
+        }
+
+        if r > zero {
+            bvn + normal_cdf(-h.max(k))
+        } else if h >= k {
+            -bvn
+        } else {
+            let l = if h < zero {
+                normal_cdf(k) - normal_cdf(h)
+            } else {
+                normal_cdf(-h) - normal_cdf(-k)
+            };
+            l - bvn
+        }
+    };
+
+    bvn.max(zero).min(one)
+}
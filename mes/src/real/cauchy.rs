@@ -0,0 +1,103 @@
+//! Implementation of (univariate) Cauchy distributions.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use core::cmp::Ordering;
+use num_traits::{Float, FloatConst};
+
+use super::{
+    interval::Interval, weighted::Weighted, ContinuousDistribution, Real, RealDistribution,
+};
+
+/// A (univariate) weighted Cauchy measure.
+pub type Cauchy<R> = Weighted<PCauchy<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) Cauchy distribution.
+///
+/// Its mean and variance do not exist, so they are NaN:
+/// ```
+/// use mes::real::{cauchy::PCauchy, RealDistribution};
+///
+/// let d = PCauchy {
+///     location: 0.0f64,
+///     scale: 1.0,
+/// };
+/// assert!(d.mean().is_nan());
+/// assert_eq!(d.median(), 0.0);
+/// assert!((d.cdf(1.0) - 0.75).abs() < 1e-15);
+/// assert!((d.quantile(0.75) - 1.0).abs() < 1e-15);
+/// ```
+pub struct PCauchy<R: Real> {
+    /// The location of the distribution, which is its median.
+    pub location: R,
+
+    /// The scale of the distribution, which must be positive.
+    pub scale: R,
+}
+
+impl<R: Real + Float + FloatConst> PCauchy<R> {
+    /// The probability of the values at most `location + z * scale`.
+    fn standard_cdf(z: R) -> R {
+        // Use `atan(1 / z)` in the tails, where `atan(z)` is close to ±π/2.
+        match z.partial_cmp(&R::zero()) {
+            Some(Ordering::Less) => (-Float::recip(z)).atan() / R::PI(),
+            Some(Ordering::Greater) => R::one() - Float::recip(z).atan() / R::PI(),
+            Some(Ordering::Equal) => Float::recip(R::one() + R::one()),
+            None => z,
+        }
+    }
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PCauchy<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        Self::standard_cdf((x - self.location) / self.scale)
+    }
+
+    fn survival(&self, x: R) -> R {
+        Self::standard_cdf((self.location - x) / self.scale)
+    }
+
+    fn quantile(&self, p: R) -> R {
+        let half = Float::recip(R::one() + R::one());
+        if !(R::zero()..=R::one()).contains(&p) {
+            <R as Float>::nan()
+        } else if p.is_zero() {
+            <R as Float>::neg_infinity()
+        } else if p == R::one() {
+            <R as Float>::infinity()
+        } else {
+            self.location + self.scale * (R::PI() * (p - half)).tan()
+        }
+    }
+
+    fn mean(&self) -> R {
+        <R as Float>::nan()
+    }
+
+    fn variance(&self) -> R {
+        <R as Float>::nan()
+    }
+
+    fn median(&self) -> R {
+        self.location
+    }
+
+    fn mode(&self) -> R {
+        self.location
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::full()
+    }
+}
+
+impl<R: Real + Float + FloatConst> ContinuousDistribution for PCauchy<R> {
+    fn density(&self, x: R) -> R {
+        let z = (x - self.location) / self.scale;
+        R::one() / (R::PI() * self.scale * (R::one() + z * z))
+    }
+}
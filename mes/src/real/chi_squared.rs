@@ -0,0 +1,86 @@
+//! Implementation of (univariate) chi-squared distributions.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use num_traits::{Float, FloatConst};
+
+use super::{
+    gamma::PGamma, interval::Interval, weighted::Weighted, ContinuousDistribution, Real,
+    RealDistribution,
+};
+
+/// A (univariate) weighted chi-squared measure.
+pub type ChiSquared<R> = Weighted<PChiSquared<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) chi-squared distribution, i.e., the distribution of the sum
+/// of the squares of independent standard Gaussian random variables.
+///
+/// ```
+/// use mes::real::{chi_squared::PChiSquared, RealDistribution};
+///
+/// let d = PChiSquared {
+///     degrees_of_freedom: 3.0f64,
+/// };
+/// assert_eq!(d.mean(), 3.0);
+/// assert_eq!(d.variance(), 6.0);
+/// assert!((d.cdf(7.814727903251178) - 0.95).abs() < 1e-12);
+/// ```
+pub struct PChiSquared<R: Real> {
+    /// The number of degrees of freedom, which must be positive.
+    pub degrees_of_freedom: R,
+}
+
+impl<R: Real + Float + FloatConst> PChiSquared<R> {
+    /// The equivalent gamma distribution.
+    pub fn gamma(&self) -> PGamma<R> {
+        let two = R::one() + R::one();
+        PGamma {
+            shape: self.degrees_of_freedom / two,
+            scale: two,
+        }
+    }
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PChiSquared<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        self.gamma().cdf(x)
+    }
+
+    fn survival(&self, x: R) -> R {
+        self.gamma().survival(x)
+    }
+
+    fn quantile(&self, p: R) -> R {
+        self.gamma().quantile(p)
+    }
+
+    fn mean(&self) -> R {
+        self.degrees_of_freedom
+    }
+
+    fn variance(&self) -> R {
+        self.degrees_of_freedom + self.degrees_of_freedom
+    }
+
+    fn median(&self) -> R {
+        self.gamma().median()
+    }
+
+    fn mode(&self) -> R {
+        self.gamma().mode()
+    }
+
+    fn support(&self) -> Interval<R> {
+        self.gamma().support()
+    }
+}
+
+impl<R: Real + Float + FloatConst> ContinuousDistribution for PChiSquared<R> {
+    fn density(&self, x: R) -> R {
+        self.gamma().density(x)
+    }
+}
@@ -4,12 +4,26 @@ use core::ops::{Mul, MulAssign};
 use num_traits::float::FloatCore;
 use with_locals::with;
 
-use crate::{DiracMeasure, Measurable, Measure, PointMeasure};
+use crate::{
+    ConditionableMeasure, DiracMeasure, IntegrableMeasure, Measurable, Measure, PointMeasure,
+};
 
-use super::Real;
+use super::{interval::Interval, Real, RealDistribution, RealSubset};
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 ///A (univariate) weighted Dirac delta measure.
+///
+/// All of the weight is assigned to the subsets containing the point:
+/// ```
+/// use mes::{real::dirac::Dirac, Measure, PointMeasurable};
+///
+/// let m = Dirac {
+///     point: 1.5f64,
+///     weight: 2.0,
+/// };
+/// assert_eq!(1.5f64.with_point_subset(|s| m.with_measure(s, |x| *x)), 2.0);
+/// assert_eq!(1.0f64.with_point_subset(|s| m.with_measure(s, |x| *x)), 0.0);
+/// ```
 pub struct Dirac<R: Real> {
     /// The location of the point mass.
     pub point: R,
@@ -18,7 +32,7 @@ pub struct Dirac<R: Real> {
     pub weight: R,
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
 ///A (univariate) Dirac delta probability measure.
 pub struct PDirac<R: Real> {
     /// The location of the point mass.
@@ -102,3 +116,59 @@ impl<'subset, R: Real + FloatCore> DiracMeasure<'subset> for Dirac<R> {
         }
     }
 }
+
+impl<'subset, R: Real + FloatCore> ConditionableMeasure<'subset> for Dirac<R> {
+    fn condition(&self, event: &(dyn RealSubset<R> + '_)) -> Option<Self::PMeasure> {
+        if event.contains(&self.point) {
+            self.normalize()
+        } else {
+            None
+        }
+    }
+}
+
+impl<'subset, R: Real + FloatCore> IntegrableMeasure<'subset> for Dirac<R> {
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        self.weight * f(&self.point)
+    }
+}
+
+impl<R: Real> RealDistribution for PDirac<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        if x >= self.point {
+            R::one()
+        } else {
+            R::zero()
+        }
+    }
+
+    fn quantile(&self, p: R) -> R {
+        if (R::zero()..=R::one()).contains(&p) {
+            self.point
+        } else {
+            R::nan()
+        }
+    }
+
+    fn mean(&self) -> R {
+        self.point
+    }
+
+    fn variance(&self) -> R {
+        R::zero()
+    }
+
+    fn median(&self) -> R {
+        self.point
+    }
+
+    fn mode(&self) -> R {
+        self.point
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::point(self.point)
+    }
+}
@@ -0,0 +1,87 @@
+//! Implementation of (univariate) exponential distributions.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use num_traits::{Float, FloatConst};
+
+use super::{
+    interval::Interval, weighted::Weighted, ContinuousDistribution, Real, RealDistribution,
+};
+
+/// A (univariate) weighted exponential measure.
+pub type Exponential<R> = Weighted<PExponential<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) exponential distribution.
+///
+/// ```
+/// use mes::real::{exponential::PExponential, RealDistribution};
+///
+/// let d = PExponential { rate: 0.5f64 };
+/// assert_eq!(d.mean(), 2.0);
+/// assert_eq!(d.survival(2.0), (-1f64).exp());
+/// assert!((d.median() - 2.0 * 2f64.ln()).abs() < 1e-15);
+/// ```
+pub struct PExponential<R: Real> {
+    /// The rate of the distribution, which must be positive.
+    pub rate: R,
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PExponential<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::zero()
+        } else {
+            -(-self.rate * x).exp_m1()
+        }
+    }
+
+    fn survival(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::one()
+        } else {
+            (-self.rate * x).exp()
+        }
+    }
+
+    fn quantile(&self, p: R) -> R {
+        if !(R::zero()..=R::one()).contains(&p) {
+            <R as Float>::nan()
+        } else {
+            -(-p).ln_1p() / self.rate
+        }
+    }
+
+    fn mean(&self) -> R {
+        R::one() / self.rate
+    }
+
+    fn variance(&self) -> R {
+        R::one() / (self.rate * self.rate)
+    }
+
+    fn median(&self) -> R {
+        R::LN_2() / self.rate
+    }
+
+    fn mode(&self) -> R {
+        R::zero()
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::closed(R::zero(), <R as Float>::infinity())
+    }
+}
+
+impl<R: Real + Float + FloatConst> ContinuousDistribution for PExponential<R> {
+    fn density(&self, x: R) -> R {
+        if x < R::zero() {
+            R::zero()
+        } else {
+            self.rate * (-self.rate * x).exp()
+        }
+    }
+}
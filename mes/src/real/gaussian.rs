@@ -5,25 +5,164 @@
 
 use core::ops::{Mul, MulAssign};
 use derive_more::{Add, AddAssign};
+use num_traits::{Float, FloatConst};
+use with_locals::with;
 
-use crate::measure::Measure;
+use crate::{
+    special::{erfc, erfc_inv},
+    DiracMeasure, Measurable, Measure, PointMeasure,
+};
 
-use super::{dirac::Dirac, Real};
+use super::{
+    continuous_probability,
+    dirac::{Dirac, PDirac},
+    interval::Interval,
+    Real, RealDistribution, RealSubset,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 /// A (univariate) weighted Gaussian measure.
+///
+/// Subsets are measured exactly through the normal CDF, provided that they can
+/// be decomposed into intervals:
+/// ```
+/// use mes::{
+///     real::{
+///         gaussian::{Gaussian, PGaussian},
+///         interval::Interval,
+///     },
+///     Measurable, Measure, PointMeasurable,
+/// };
+///
+/// let m = Gaussian {
+///     distribution: PGaussian {
+///         mean: 1.0f64,
+///         variance: 4.0,
+///     },
+///     weight: 2.0,
+/// };
+///
+/// // One standard deviation either side of the mean.
+/// let x = m.with_measure(&Interval::open(-1.0, 3.0), |x| *x);
+/// assert!((x - 2.0 * 0.682689492137086).abs() < 1e-12);
+///
+/// // The complement of a point has full weight.
+/// 1.0f64.with_point_subset(|p| {
+///     f64::with_subset_complement(p, |s| assert_eq!(m.with_measure(s, |x| *x), 2.0));
+/// });
+/// ```
 pub struct Gaussian<R: Real> {
+    /// The distribution of the measure.
     pub distribution: PGaussian<R>,
+
+    /// The weight of the measure.
     pub weight: R,
 }
 
 /// A (univariate) Gaussian distribution.
+///
+/// Adding distributions gives the distribution of the sum of independent
+/// random variables with those distributions.
 #[derive(Debug, Clone, Copy, PartialEq, Add, AddAssign)]
 pub struct PGaussian<R: Real> {
+    /// The mean of the distribution.
     pub mean: R,
+
+    /// The variance of the distribution, which may be zero.
     pub variance: R,
 }
 
+impl<R: Real + Float + FloatConst> PGaussian<R> {
+    /// The density of the distribution at the given point.
+    ///
+    /// With zero variance, this is infinite at the mean, as for a Dirac
+    /// measure.
+    pub fn density(&self, value: R) -> R {
+        if self.variance.is_zero() {
+            return if value == self.mean {
+                <R as Float>::infinity()
+            } else {
+                R::zero()
+            };
+        }
+
+        let offset = value - self.mean;
+        (-offset * offset / (self.variance + self.variance)).exp()
+            / (self.variance * R::TAU()).sqrt()
+    }
+
+    /// Computes `(x - mean) / sqrt(2 * variance)`, so that the CDF is
+    /// `erfc(-z) / 2`.
+    fn standardize(&self, x: R) -> R {
+        (x - self.mean) / (self.variance + self.variance).sqrt()
+    }
+
+    /// The probability of the given subset.
+    pub(super) fn probability(&self, domain: &(dyn RealSubset<R> + '_)) -> R {
+        if self.variance.is_zero() {
+            return if domain.contains(&self.mean) {
+                R::one()
+            } else {
+                R::zero()
+            };
+        }
+
+        continuous_probability(self, domain)
+    }
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PGaussian<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        if self.variance.is_zero() {
+            return PDirac { point: self.mean }.cdf(x);
+        }
+
+        erfc(-self.standardize(x)) / (R::one() + R::one())
+    }
+
+    fn survival(&self, x: R) -> R {
+        if self.variance.is_zero() {
+            return PDirac { point: self.mean }.survival(x);
+        }
+
+        erfc(self.standardize(x)) / (R::one() + R::one())
+    }
+
+    fn quantile(&self, p: R) -> R {
+        if self.variance.is_zero() {
+            return PDirac { point: self.mean }.quantile(p);
+        }
+
+        self.mean - (self.variance + self.variance).sqrt() * erfc_inv(p + p)
+    }
+
+    fn mean(&self) -> R {
+        self.mean
+    }
+
+    fn variance(&self) -> R {
+        self.variance
+    }
+
+    fn median(&self) -> R {
+        self.mean
+    }
+
+    fn mode(&self) -> R {
+        self.mean
+    }
+
+    fn support(&self) -> Interval<R> {
+        if self.variance.is_zero() {
+            Interval::point(self.mean)
+        } else {
+            Interval::full()
+        }
+    }
+}
+
 impl<R: Real> From<Dirac<R>> for Gaussian<R> {
     fn from(m: Dirac<R>) -> Self {
         Self {
@@ -62,25 +201,28 @@ impl<R: Real> MulAssign<R> for Gaussian<R> {
     }
 }
 
-impl<R: Real> Measure for Gaussian<R> {
+impl<'subset, R: Real + Float + FloatConst> Measure<'subset> for Gaussian<R> {
     type R = R;
 
     type Space = R;
 
-    type Measurement<'a> = R
-    where
-        Self: 'a;
+    type Measurement = R;
 
     type PMeasure = PGaussian<R>;
 
-    fn measure_at(&self, value: &Self::Space) -> Self::Measurement<'_> {
-        let half = (R::one() + R::one()).recip();
-
-        let PGaussian { mean, variance } = self.distribution;
-        let offset = *value - mean;
-        self.weight
-            * (variance * R::two_pi()).sqrt().recip()
-            * (-half / variance * offset * offset).exp()
+    #[with]
+    /// Computes the measure of the given subset.
+    ///
+    /// The measurement is NaN if the subset is neither empty nor full and
+    /// cannot be decomposed into intervals, unless the variance is zero.
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &(self.weight * self.distribution.probability(domain))
     }
 
     fn normalize(&self) -> Option<Self::PMeasure> {
@@ -88,3 +230,18 @@ impl<R: Real> Measure for Gaussian<R> {
         Some(self.distribution)
     }
 }
+
+impl<'subset, R: Real + Float + FloatConst> PointMeasure<'subset> for Gaussian<R> {
+    type PointMeasurement = R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &(self.weight * self.distribution.density(*value))
+    }
+}
+
+impl<'subset, R: Real + Float + FloatConst> DiracMeasure<'subset> for Gaussian<R> {
+    fn dirac(point: &Self::Space) -> Self {
+        Dirac::dirac(point).into()
+    }
+}
@@ -0,0 +1,556 @@
+//! Intervals and finite unions of intervals of the real line.
+
+use core::cmp::Ordering;
+
+#[cfg(feature = "alloc")]
+use {
+    alloc::vec::Vec,
+    core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign},
+};
+
+use super::{Real, RealSubset};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// An interval of the real line, each end of which is either open or closed.
+///
+/// Infinite ends are always considered closed, so that intervals of the
+/// extended real line can be complemented exactly.
+pub struct Interval<R> {
+    /// The lower end of the interval.
+    pub lower: R,
+
+    /// The upper end of the interval.
+    pub upper: R,
+
+    /// Whether the interval contains its lower end.
+    pub lower_closed: bool,
+
+    /// Whether the interval contains its upper end.
+    pub upper_closed: bool,
+}
+
+impl<R: Real> Interval<R> {
+    /// Constructs the interval with the given ends.
+    pub fn new(lower: R, upper: R, lower_closed: bool, upper_closed: bool) -> Self {
+        Self {
+            lower,
+            upper,
+            lower_closed: lower_closed || lower.is_infinite(),
+            upper_closed: upper_closed || upper.is_infinite(),
+        }
+    }
+
+    /// Constructs the closed interval `[lower, upper]`.
+    pub fn closed(lower: R, upper: R) -> Self {
+        Self::new(lower, upper, true, true)
+    }
+
+    /// Constructs the open interval `(lower, upper)`.
+    pub fn open(lower: R, upper: R) -> Self {
+        Self::new(lower, upper, false, false)
+    }
+
+    /// Constructs the interval containing only the given point.
+    pub fn point(value: R) -> Self {
+        Self::closed(value, value)
+    }
+
+    /// Constructs the interval containing the entire real line.
+    pub fn full() -> Self {
+        Self::closed(R::neg_infinity(), R::infinity())
+    }
+
+    /// Checks whether the interval is empty.
+    pub fn is_empty(&self) -> bool {
+        match self.lower.partial_cmp(&self.upper) {
+            Some(Ordering::Less) => false,
+            Some(Ordering::Equal) => !(self.lower_closed && self.upper_closed),
+            _ => true,
+        }
+    }
+
+    /// Checks whether the interval contains the entire real line.
+    pub fn is_full(&self) -> bool {
+        self.lower == R::neg_infinity() && self.upper == R::infinity()
+    }
+
+    /// Checks whether the interval contains the given value.
+    pub fn contains(&self, value: &R) -> bool {
+        let above = if self.lower_closed {
+            self.lower <= *value
+        } else {
+            self.lower < *value
+        };
+        let below = if self.upper_closed {
+            *value <= self.upper
+        } else {
+            *value < self.upper
+        };
+        above && below
+    }
+}
+
+impl<R: Real> RealSubset<R> for Interval<R> {
+    fn is_empty(&self) -> bool {
+        Interval::is_empty(self)
+    }
+
+    fn is_full(&self) -> bool {
+        Interval::is_full(self)
+    }
+
+    fn contains(&self, value: &R) -> bool {
+        Interval::contains(self, value)
+    }
+
+    fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+        Some(clip(*self, (from, from_closed)))
+    }
+}
+
+/// An iterator over the maximal intervals making up a subset of the real line,
+/// in increasing order.
+///
+/// Returned by the `intervals` method of [`RealSubset`] trait objects.
+pub struct Intervals<'a, R> {
+    subset: &'a (dyn RealSubset<R> + 'a),
+    start: (R, bool),
+}
+
+impl<'a, R: Real> Iterator for Intervals<'a, R> {
+    type Item = Interval<R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (from, from_closed) = self.start;
+        let interval = self.subset.next_interval(from, from_closed)??;
+        self.start = after(&interval);
+        Some(interval)
+    }
+}
+
+impl<'a, R: Real> dyn RealSubset<R> + 'a {
+    /// Iterates over the maximal intervals making up the subset, in increasing
+    /// order.
+    ///
+    /// Returns [`None`] if the subset does not support decomposition into
+    /// intervals (see [`RealSubset::next_interval`]). Otherwise, continuous
+    /// measures can measure the subset by summing over its intervals:
+    /// ```
+    /// use mes::{
+    ///     real::{interval::Interval, RealSubset},
+    ///     Measurable,
+    /// };
+    ///
+    /// let s = Interval::closed(0.0f64, 2.0);
+    /// let t = Interval::open(1.0f64, 5.0);
+    /// let length: f64 = f64::with_subset_symmetric_difference(&s, &t, |d| {
+    ///     d.intervals().unwrap().map(|i| i.upper - i.lower).sum()
+    /// });
+    /// assert_eq!(length, 4.0);
+    /// ```
+    pub fn intervals(&self) -> Option<Intervals<'_, R>> {
+        let start = (R::neg_infinity(), true);
+        self.next_interval(start.0, start.1)?;
+        Some(Intervals {
+            subset: self,
+            start,
+        })
+    }
+}
+
+/// The start of a range of the real line, given as a value and whether the
+/// range includes it.
+type Start<R> = (R, bool);
+
+/// The result of [`RealSubset::next_interval`].
+type Next<R> = Option<Option<Interval<R>>>;
+
+/// Checks whether the range starting at `a` starts strictly later than the one
+/// starting at `b`.
+fn starts_later<R: Real>(a: Start<R>, b: Start<R>) -> bool {
+    a.0 > b.0 || a.0 == b.0 && b.1 && !a.1
+}
+
+/// Checks whether the interval `a` ends strictly earlier than the interval `b`.
+fn ends_earlier<R: Real>(a: &Interval<R>, b: &Interval<R>) -> bool {
+    a.upper < b.upper || a.upper == b.upper && b.upper_closed && !a.upper_closed
+}
+
+/// The start of the range immediately following the given interval.
+fn after<R: Real>(interval: &Interval<R>) -> Start<R> {
+    (interval.upper, !interval.upper_closed)
+}
+
+/// Restricts an interval to the range beginning at `start`, giving [`None`] if
+/// the result is empty.
+fn clip<R: Real>(mut interval: Interval<R>, start: Start<R>) -> Option<Interval<R>> {
+    if starts_later(start, (interval.lower, interval.lower_closed)) {
+        (interval.lower, interval.lower_closed) = start;
+    }
+
+    (!interval.is_empty()).then_some(interval)
+}
+
+/// Finds the next interval of the complement of a subset, given the
+/// [`RealSubset::next_interval`] of the subset.
+pub(crate) fn complement_next<R: Real>(
+    next: impl Fn(R, bool) -> Next<R>,
+    from: Start<R>,
+) -> Next<R> {
+    let mut start = from;
+    loop {
+        match next(start.0, start.1)? {
+            None => return Some(clip(Interval::full(), start)),
+            // The subset contains `start`, so the complement resumes after the
+            // (maximal) interval containing it.
+            Some(i) if (i.lower, i.lower_closed) == start => start = after(&i),
+            Some(i) => {
+                return Some(Some(Interval {
+                    lower: start.0,
+                    upper: i.lower,
+                    lower_closed: start.1,
+                    upper_closed: !i.lower_closed,
+                }))
+            }
+        }
+    }
+}
+
+/// Finds the next interval of the union of some subsets, given a function
+/// which passes the [`RealSubset::next_interval`] of each subset to a callback.
+pub(crate) fn union_next<R: Real>(
+    mut members: impl FnMut(Start<R>, &mut dyn FnMut(Option<Interval<R>>)) -> Option<()>,
+    from: Start<R>,
+) -> Next<R> {
+    let mut first: Option<Interval<R>> = None;
+    members(from, &mut |i| {
+        if let Some(i) = i {
+            if first
+                .is_none_or(|f| starts_later((f.lower, f.lower_closed), (i.lower, i.lower_closed)))
+            {
+                first = Some(i);
+            }
+        }
+    })?;
+
+    let Some(mut result) = first else {
+        return Some(None);
+    };
+    loop {
+        // Extend the interval by any intervals which continue it.
+        let end = after(&result);
+        let mut extended = false;
+        members(end, &mut |i| {
+            if let Some(i) = i {
+                if (i.lower, i.lower_closed) == end && ends_earlier(&result, &i) {
+                    result.upper = i.upper;
+                    result.upper_closed = i.upper_closed;
+                    extended = true;
+                }
+            }
+        })?;
+
+        if !extended {
+            return Some(Some(result));
+        }
+    }
+}
+
+/// Finds the next interval of the intersection of some subsets, given a
+/// function which passes the [`RealSubset::next_interval`] of each subset to a
+/// callback.
+pub(crate) fn intersection_next<R: Real>(
+    mut members: impl FnMut(Start<R>, &mut dyn FnMut(Option<Interval<R>>)) -> Option<()>,
+    from: Start<R>,
+) -> Next<R> {
+    let mut start = from;
+    loop {
+        let mut latest = start;
+        let mut result = Interval::full();
+        let mut exhausted = false;
+        members(start, &mut |i| match i {
+            None => exhausted = true,
+            Some(i) => {
+                if starts_later((i.lower, i.lower_closed), latest) {
+                    latest = (i.lower, i.lower_closed);
+                }
+                if ends_earlier(&i, &result) {
+                    result.upper = i.upper;
+                    result.upper_closed = i.upper_closed;
+                }
+            }
+        })?;
+
+        if exhausted {
+            return Some(None);
+        }
+        if latest == start {
+            // Every subset contains `start`.
+            return Some(clip(result, start));
+        }
+        start = latest;
+    }
+}
+
+/// Finds the next interval of the set difference `s - t`.
+pub(crate) fn difference_next<R: Real>(
+    s: &(dyn RealSubset<R> + '_),
+    t: &(dyn RealSubset<R> + '_),
+    from: Start<R>,
+) -> Next<R> {
+    intersection_next(
+        |(a, b), f| {
+            f(s.next_interval(a, b)?);
+            f(complement_next(|c, d| t.next_interval(c, d), (a, b))?);
+            Some(())
+        },
+        from,
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq)]
+/// A subset of the real line which is a finite union of intervals.
+///
+/// The intervals are kept sorted and disjoint, with no two of them touching,
+/// so set operations are exact and the representation of each subset is
+/// unique:
+/// ```
+/// use mes::real::{
+///     interval::{Interval, IntervalSet},
+///     RealSubset,
+/// };
+///
+/// let s = IntervalSet::from(Interval::closed(0.0f64, 1.0))
+///     | IntervalSet::from(Interval::open(1.0, 2.0))
+///     | IntervalSet::from(Interval::point(3.0));
+/// assert_eq!(
+///     s.intervals(),
+///     [Interval::new(0.0, 2.0, true, false), Interval::point(3.0)]
+/// );
+///
+/// let c = !s.clone();
+/// assert!(c.contains(&2.0) && !c.contains(&3.0));
+/// assert!((s.clone() | c.clone()).is_full());
+/// assert!((s & c).is_empty());
+/// ```
+pub struct IntervalSet<R> {
+    intervals: Vec<Interval<R>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> IntervalSet<R> {
+    /// Constructs the empty subset.
+    pub fn empty() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Constructs the subset containing the entire real line.
+    pub fn full() -> Self {
+        Interval::full().into()
+    }
+
+    /// The disjoint intervals making up the subset, in increasing order.
+    pub fn intervals(&self) -> &[Interval<R>] {
+        &self.intervals
+    }
+
+    /// Checks whether the subset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Checks whether the subset contains the entire real line.
+    pub fn is_full(&self) -> bool {
+        self.intervals.len() == 1 && self.intervals[0].is_full()
+    }
+
+    /// Checks whether the subset contains the given value.
+    pub fn contains(&self, value: &R) -> bool {
+        // The intervals are sorted, so only the last one starting at or before
+        // `value` can contain it.
+        let index = self.intervals.partition_point(|i| i.lower <= *value);
+        index > 0 && self.intervals[index - 1].contains(value)
+    }
+
+    /// Computes the complement of the subset.
+    pub fn complement(&self) -> Self {
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        let mut lower = R::neg_infinity();
+        let mut lower_closed = true;
+        for i in &self.intervals {
+            intervals.push(Interval {
+                lower,
+                upper: i.lower,
+                lower_closed,
+                upper_closed: !i.lower_closed,
+            });
+            lower = i.upper;
+            lower_closed = !i.upper_closed;
+        }
+        intervals.push(Interval {
+            lower,
+            upper: R::infinity(),
+            lower_closed,
+            upper_closed: true,
+        });
+
+        intervals.retain(|i| !i.is_empty());
+        Self { intervals }
+    }
+
+    /// Computes the union of two subsets.
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    /// Computes the intersection of two subsets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.complement().union(&other.complement()).complement()
+    }
+
+    /// Computes the set difference of two subsets.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> Default for IntervalSet<R> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> From<Interval<R>> for IntervalSet<R> {
+    fn from(interval: Interval<R>) -> Self {
+        core::iter::once(interval).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> FromIterator<Interval<R>> for IntervalSet<R> {
+    /// Constructs the union of the given intervals.
+    fn from_iter<I: IntoIterator<Item = Interval<R>>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval<R>> = iter
+            .into_iter()
+            .map(|i| Interval::new(i.lower, i.upper, i.lower_closed, i.upper_closed))
+            .filter(|i| !i.is_empty())
+            .collect();
+        sorted.sort_by(|a, b| {
+            a.lower
+                .partial_cmp(&b.lower)
+                .unwrap_or(Ordering::Equal)
+                .then(b.lower_closed.cmp(&a.lower_closed))
+        });
+
+        let mut intervals: Vec<Interval<R>> = Vec::with_capacity(sorted.len());
+        for i in sorted {
+            match intervals.last_mut() {
+                Some(last)
+                    if i.lower < last.upper
+                        || i.lower == last.upper && (i.lower_closed || last.upper_closed) =>
+                {
+                    if i.upper > last.upper {
+                        last.upper = i.upper;
+                        last.upper_closed = i.upper_closed;
+                    } else if i.upper == last.upper {
+                        last.upper_closed |= i.upper_closed;
+                    }
+                }
+                _ => intervals.push(i),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> Not for IntervalSet<R> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> BitOr for IntervalSet<R> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> BitAnd for IntervalSet<R> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> Sub for IntervalSet<R> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> BitOrAssign for IntervalSet<R> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(&rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> BitAndAssign for IntervalSet<R> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(&rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Real> SubAssign for IntervalSet<R> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.difference(&rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<R: Real> RealSubset<R> for IntervalSet<R> {
+    fn is_empty(&self) -> bool {
+        IntervalSet::is_empty(self)
+    }
+
+    fn is_full(&self) -> bool {
+        IntervalSet::is_full(self)
+    }
+
+    fn contains(&self, value: &R) -> bool {
+        IntervalSet::contains(self, value)
+    }
+
+    fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+        Some(
+            self.intervals
+                .iter()
+                .find_map(|i| clip(*i, (from, from_closed))),
+        )
+    }
+}
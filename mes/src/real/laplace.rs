@@ -0,0 +1,98 @@
+//! Implementation of (univariate) Laplace distributions.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use num_traits::{Float, FloatConst};
+
+use super::{
+    interval::Interval, weighted::Weighted, ContinuousDistribution, Real, RealDistribution,
+};
+
+/// A (univariate) weighted Laplace measure.
+pub type Laplace<R> = Weighted<PLaplace<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) Laplace distribution.
+///
+/// ```
+/// use mes::real::{laplace::PLaplace, RealDistribution};
+///
+/// let d = PLaplace {
+///     location: 1.0f64,
+///     scale: 2.0,
+/// };
+/// assert_eq!(d.cdf(1.0), 0.5);
+/// assert_eq!(d.variance(), 8.0);
+/// assert!((d.cdf(d.quantile(0.1)) - 0.1).abs() < 1e-15);
+/// ```
+pub struct PLaplace<R: Real> {
+    /// The location of the distribution, which is its mean.
+    pub location: R,
+
+    /// The scale of the distribution, which must be positive.
+    pub scale: R,
+}
+
+impl<R: Real + Float + FloatConst> PLaplace<R> {
+    /// The probability of the values at most `location + z * scale`.
+    fn standard_cdf(z: R) -> R {
+        let half = Float::recip(R::one() + R::one());
+        if z < R::zero() {
+            half * z.exp()
+        } else {
+            R::one() - half * (-z).exp()
+        }
+    }
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PLaplace<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        Self::standard_cdf((x - self.location) / self.scale)
+    }
+
+    fn survival(&self, x: R) -> R {
+        Self::standard_cdf((self.location - x) / self.scale)
+    }
+
+    fn quantile(&self, p: R) -> R {
+        let two = R::one() + R::one();
+        if !(R::zero()..=R::one()).contains(&p) {
+            <R as Float>::nan()
+        } else if p + p < R::one() {
+            self.location + self.scale * (two * p).ln()
+        } else {
+            self.location - self.scale * (two - two * p).ln()
+        }
+    }
+
+    fn mean(&self) -> R {
+        self.location
+    }
+
+    fn variance(&self) -> R {
+        let two = R::one() + R::one();
+        two * self.scale * self.scale
+    }
+
+    fn median(&self) -> R {
+        self.location
+    }
+
+    fn mode(&self) -> R {
+        self.location
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::full()
+    }
+}
+
+impl<R: Real + Float + FloatConst> ContinuousDistribution for PLaplace<R> {
+    fn density(&self, x: R) -> R {
+        let z = (x - self.location) / self.scale;
+        (-Float::abs(z)).exp() / (self.scale + self.scale)
+    }
+}
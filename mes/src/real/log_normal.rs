@@ -0,0 +1,92 @@
+//! Implementation of (univariate) log-normal distributions.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use num_traits::{Float, FloatConst};
+
+use super::{
+    gaussian::PGaussian, interval::Interval, weighted::Weighted, ContinuousDistribution, Real,
+    RealDistribution,
+};
+
+/// A (univariate) weighted log-normal measure.
+pub type LogNormal<R> = Weighted<PLogNormal<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) log-normal distribution, i.e., the distribution of `e^X` for
+/// a Gaussian random variable `X`.
+///
+/// ```
+/// use mes::real::{gaussian::PGaussian, log_normal::PLogNormal, RealDistribution};
+///
+/// let d = PLogNormal {
+///     log: PGaussian {
+///         mean: 0.0f64,
+///         variance: 1.0,
+///     },
+/// };
+/// assert_eq!(d.median(), 1.0);
+/// assert_eq!(d.cdf(1.0), 0.5);
+/// assert_eq!(d.mean(), 0.5f64.exp());
+/// ```
+pub struct PLogNormal<R: Real> {
+    /// The distribution of the logarithm, whose variance must be positive.
+    pub log: PGaussian<R>,
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PLogNormal<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::zero()
+        } else {
+            self.log.cdf(x.ln())
+        }
+    }
+
+    fn survival(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::one()
+        } else {
+            self.log.survival(x.ln())
+        }
+    }
+
+    fn quantile(&self, p: R) -> R {
+        self.log.quantile(p).exp()
+    }
+
+    fn mean(&self) -> R {
+        let half = Float::recip(R::one() + R::one());
+        (self.log.mean + half * self.log.variance).exp()
+    }
+
+    fn variance(&self) -> R {
+        let PGaussian { mean, variance } = self.log;
+        variance.exp_m1() * (mean + mean + variance).exp()
+    }
+
+    fn median(&self) -> R {
+        self.log.mean.exp()
+    }
+
+    fn mode(&self) -> R {
+        (self.log.mean - self.log.variance).exp()
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::closed(R::zero(), <R as Float>::infinity())
+    }
+}
+
+impl<R: Real + Float + FloatConst> ContinuousDistribution for PLogNormal<R> {
+    fn density(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::zero()
+        } else {
+            self.log.density(x.ln()) / x
+        }
+    }
+}
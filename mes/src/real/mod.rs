@@ -1,12 +1,30 @@
 //! Facilities for working with real numbers.
 
-use num_traits::{float::FloatCore, NumAssign};
+use num_traits::{float::FloatCore, NumAssign, One, Zero};
 use with_locals::with;
 
-use crate::{all, any, util::iter::LocalIterator, Measurable, SubsetProxy};
+use crate::{all, any, util::iter::LocalIterator, Measurable, PointMeasurable, SubsetProxy};
 
+use interval::{complement_next, difference_next, intersection_next, union_next, Interval};
+
+#[cfg(feature = "alloc")]
+use {crate::OwnedMeasurable, interval::IntervalSet};
+
+pub mod beta;
+pub mod bivariate_gaussian;
+pub mod cauchy;
+pub mod chi_squared;
 pub mod dirac;
-// pub mod gaussian;
+pub mod exponential;
+pub mod gamma;
+pub mod gaussian;
+pub mod interval;
+pub mod laplace;
+pub mod log_normal;
+pub mod student_t;
+pub mod uniform;
+pub mod weibull;
+pub mod weighted;
 
 /// Describes a type which represents a real number.
 pub trait Real: FloatCore + NumAssign + Copy + 'static {
@@ -44,13 +62,51 @@ impl_real!(f64);
 /// Describes a subset of the real number line.
 pub trait RealSubset<R: Real> {
     /// Checks whether the subset is empty.
+    ///
+    /// ```
+    /// use mes::{real::interval::Interval, Measurable};
+    ///
+    /// let s = Interval::closed(0.0f64, 1.0);
+    /// let t = Interval::closed(0.0f64, 2.0);
+    /// f64::with_subset_difference(&s, &t, |d| assert!(d.is_empty()));
+    /// f64::with_subset_difference(&t, &s, |d| assert!(!d.is_empty()));
+    /// f64::with_subset_symmetric_difference(&s, &s, |d| assert!(d.is_empty()));
+    /// ```
     fn is_empty(&self) -> bool;
 
     /// Checks whether the subset is full (i.e., contains the entire real line).
+    ///
+    /// ```
+    /// use mes::{real::interval::Interval, Measurable};
+    ///
+    /// let s = Interval::new(0.0f64, 1.0, true, false);
+    /// f64::with_subset_complement(&s, |c| {
+    ///     f64::with_subset_symmetric_difference(&s, c, |d| assert!(d.is_full()));
+    ///     f64::with_subset_difference(&Interval::full(), c, |d| assert!(f64::subset_eq(d, &s)));
+    /// });
+    /// ```
     fn is_full(&self) -> bool;
 
     /// Checks whether the subset contains the given value.
     fn contains(&self, value: &R) -> bool;
+
+    /// Finds the first of the maximal intervals making up the subset which
+    /// contains points in the range starting at `from` (inclusive if
+    /// `from_closed`), restricted to that range.
+    ///
+    /// Returns [`None`] if the subset does not support decomposition into
+    /// intervals, which is the default, and `Some(None)` if the subset contains
+    /// no points in the range. The subsets provided by [`Measurable`] support
+    /// decomposition whenever the subsets they are built from do, in which
+    /// case their [`is_empty`](Self::is_empty) and [`is_full`](Self::is_full)
+    /// are exact.
+    ///
+    /// Implementations must return maximal intervals, i.e., the returned
+    /// interval must not be extendable to the right within the subset.
+    fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+        let _ = (from, from_closed);
+        None
+    }
 }
 
 impl<R: Real> Measurable for R {
@@ -76,6 +132,10 @@ impl<R: Real> Measurable for R {
             fn contains(&self, _value: &R) -> bool {
                 false
             }
+
+            fn next_interval(&self, _from: R, _from_closed: bool) -> Option<Option<Interval<R>>> {
+                Some(None)
+            }
         }
 
         &EmptySubset
@@ -97,6 +157,10 @@ impl<R: Real> Measurable for R {
             fn contains(&self, _value: &R) -> bool {
                 true
             }
+
+            fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+                Interval::full().next_interval(from, from_closed)
+            }
         }
 
         &FullSubset(0)
@@ -107,6 +171,10 @@ impl<R: Real> Measurable for R {
         s.is_empty()
     }
 
+    fn subset_is_full(s: &Self::Subset<'_>) -> bool {
+        s.is_full()
+    }
+
     #[with]
     fn subset_complement(s: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
         struct InverseSubset<'x, R>(&'x (dyn RealSubset<R> + 'x));
@@ -123,6 +191,10 @@ impl<R: Real> Measurable for R {
             fn contains(&self, value: &R) -> bool {
                 !self.0.contains(value)
             }
+
+            fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+                complement_next(|a, b| self.0.next_interval(a, b), (from, from_closed))
+            }
         }
 
         &InverseSubset(s)
@@ -145,30 +217,378 @@ impl<R: Real> Measurable for R {
             }
 
             fn is_full(&self) -> bool {
-                todo!()
+                // Subsets which only cover the real line together can only be detected
+                // through their intervals.
+                any!(proxy in self.0.clone() => proxy.with_access(RealSubset::is_full))
+                    || matches!(
+                        self.next_interval(R::neg_infinity(), true),
+                        Some(Some(i)) if i.is_full()
+                    )
             }
 
             fn contains(&self, value: &R) -> bool {
                 any!(proxy in self.0.clone() => proxy.with_access(|s| s.contains(value)))
             }
+
+            fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+                union_next(
+                    |(a, b), f| {
+                        let mut i = self.0.clone();
+                        while let Some(proxy) = LocalIterator::next(&mut i) {
+                            f(proxy.with_access(|s| s.next_interval(a, b))?);
+                        }
+                        Some(())
+                    },
+                    (from, from_closed),
+                )
+            }
         }
 
         &UnionSubset(subsets)
     }
+
+    #[with]
+    fn subset_intersect<'a>(
+        subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone,
+    ) -> &'ref Self::Subset<'ref>
+    where
+        Self: 'a,
+    {
+        struct IntersectionSubset<T>(T);
+
+        impl<'x, R: Real, T: LocalIterator<Item = SubsetProxy<'x, R>> + Clone> RealSubset<R>
+            for IntersectionSubset<T>
+        {
+            fn is_empty(&self) -> bool {
+                // Disjoint non-empty subsets can only be detected through their intervals.
+                any!(proxy in self.0.clone() => proxy.with_access(RealSubset::is_empty))
+                    || matches!(self.next_interval(R::neg_infinity(), true), Some(None))
+            }
+
+            fn is_full(&self) -> bool {
+                all!(proxy in self.0.clone() => proxy.with_access(RealSubset::is_full))
+            }
+
+            fn contains(&self, value: &R) -> bool {
+                all!(proxy in self.0.clone() => proxy.with_access(|s| s.contains(value)))
+            }
+
+            fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+                intersection_next(
+                    |(a, b), f| {
+                        let mut i = self.0.clone();
+                        while let Some(proxy) = LocalIterator::next(&mut i) {
+                            f(proxy.with_access(|s| s.next_interval(a, b))?);
+                        }
+                        Some(())
+                    },
+                    (from, from_closed),
+                )
+            }
+        }
+
+        &IntersectionSubset(subsets)
+    }
+
+    #[with]
+    fn subset_difference(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
+        struct DifferenceSubset<'x, R>(&'x (dyn RealSubset<R> + 'x), &'x (dyn RealSubset<R> + 'x));
+
+        impl<'x, R: Real> RealSubset<R> for DifferenceSubset<'x, R> {
+            fn is_empty(&self) -> bool {
+                // A subset covering another can only be detected through their intervals.
+                self.0.is_empty()
+                    || self.1.is_full()
+                    || matches!(self.next_interval(R::neg_infinity(), true), Some(None))
+            }
+
+            fn is_full(&self) -> bool {
+                self.0.is_full() && self.1.is_empty()
+                    || matches!(
+                        self.next_interval(R::neg_infinity(), true),
+                        Some(Some(i)) if i.is_full()
+                    )
+            }
+
+            fn contains(&self, value: &R) -> bool {
+                self.0.contains(value) && !self.1.contains(value)
+            }
+
+            fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+                difference_next(self.0, self.1, (from, from_closed))
+            }
+        }
+
+        &DifferenceSubset(s, t)
+    }
+
+    #[with]
+    fn subset_symmetric_difference(
+        s: &Self::Subset<'_>,
+        t: &Self::Subset<'_>,
+    ) -> &'ref Self::Subset<'ref> {
+        struct SymmetricDifferenceSubset<'x, R>(
+            &'x (dyn RealSubset<R> + 'x),
+            &'x (dyn RealSubset<R> + 'x),
+        );
+
+        impl<'x, R: Real> RealSubset<R> for SymmetricDifferenceSubset<'x, R> {
+            fn is_empty(&self) -> bool {
+                // Equal subsets which are neither empty nor full, and complementary
+                // subsets, can only be detected through their intervals.
+                self.0.is_empty() && self.1.is_empty()
+                    || self.0.is_full() && self.1.is_full()
+                    || matches!(self.next_interval(R::neg_infinity(), true), Some(None))
+            }
+
+            fn is_full(&self) -> bool {
+                self.0.is_empty() && self.1.is_full()
+                    || self.0.is_full() && self.1.is_empty()
+                    || matches!(
+                        self.next_interval(R::neg_infinity(), true),
+                        Some(Some(i)) if i.is_full()
+                    )
+            }
+
+            fn contains(&self, value: &R) -> bool {
+                self.0.contains(value) != self.1.contains(value)
+            }
+
+            fn next_interval(&self, from: R, from_closed: bool) -> Option<Option<Interval<R>>> {
+                union_next(
+                    |(a, b), f| {
+                        f(difference_next(self.0, self.1, (a, b))?);
+                        f(difference_next(self.1, self.0, (a, b))?);
+                        Some(())
+                    },
+                    (from, from_closed),
+                )
+            }
+        }
+
+        &SymmetricDifferenceSubset(s, t)
+    }
 }
 
-// impl<R: Real> PointMeasurable for R {
-//     #[with]
-//     fn point_subset(&self) -> &'ref Self::Subset<'_> {
-//         &BoolSubset {
-//             includes_true: *self,
-//             includes_false: !*self,
-//         }
-//     }
-// }
+impl<R: Real> PointMeasurable for R {
+    #[with]
+    fn point_subset(&self) -> &'ref Self::Subset<'ref> {
+        &Interval::point(*self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// Subsets of the real line are owned as [`IntervalSet`]s. Subsets which do not
+/// support decomposition into intervals (see [`RealSubset::next_interval`]),
+/// such as the preimages of some user-defined functions, have no owned
+/// representation:
+/// ```
+/// use mes::{real::RealSubset, OwnedSubset};
+///
+/// struct Rationals;
+///
+/// impl RealSubset<f64> for Rationals {
+///     fn is_empty(&self) -> bool {
+///         false
+///     }
+///
+///     fn is_full(&self) -> bool {
+///         false
+///     }
+///
+///     fn contains(&self, value: &f64) -> bool {
+///         value.fract() == 0.0
+///     }
+/// }
+///
+/// assert!(OwnedSubset::<f64>::new(&Rationals).is_none());
+/// ```
+impl<R: Real> OwnedMeasurable for R {
+    type Owned = IntervalSet<R>;
+
+    fn subset_to_owned(s: &Self::Subset<'_>) -> Option<Self::Owned> {
+        s.intervals().map(Iterator::collect)
+    }
+
+    #[with]
+    fn subset_from_owned(s: &Self::Owned) -> &'ref Self::Subset<'ref> {
+        s
+    }
+}
 
 /// Describes a probability distribution over real numbers.
 pub trait RealDistribution {
     /// The type of real number used for measure values in the distribution.
     type R: Real;
+
+    /// The cumulative distribution function, i.e., the probability of the
+    /// values at most `x`.
+    fn cdf(&self, x: Self::R) -> Self::R;
+
+    /// The survival function, i.e., the probability of the values greater
+    /// than `x`.
+    fn survival(&self, x: Self::R) -> Self::R {
+        Self::R::one() - self.cdf(x)
+    }
+
+    /// The quantile function, i.e., the least `x` for which `cdf(x) >= p`.
+    ///
+    /// Returns NaN if `p` is not a probability. The quantiles of `0` and `1`
+    /// are the ends of the [`support`](Self::support).
+    ///
+    /// By default, this finds `x` by bisection on the CDF to within the
+    /// precision of [`Self::R`], which is only exact up to rounding, so
+    /// distributions with a closed form should override it:
+    /// ```
+    /// use mes::real::{interval::Interval, RealDistribution};
+    ///
+    /// struct Exponential(f64);
+    ///
+    /// impl RealDistribution for Exponential {
+    ///     type R = f64;
+    ///
+    ///     fn cdf(&self, x: f64) -> f64 {
+    ///         if x < 0.0 {
+    ///             0.0
+    ///         } else {
+    ///             1.0 - (-self.0 * x).exp()
+    ///         }
+    ///     }
+    ///
+    ///     fn mean(&self) -> f64 {
+    ///         self.0.recip()
+    ///     }
+    ///
+    ///     fn variance(&self) -> f64 {
+    ///         self.0.powi(-2)
+    ///     }
+    ///
+    ///     fn mode(&self) -> f64 {
+    ///         0.0
+    ///     }
+    ///
+    ///     fn support(&self) -> Interval<f64> {
+    ///         Interval::closed(0.0, f64::INFINITY)
+    ///     }
+    /// }
+    ///
+    /// let m = Exponential(2.0);
+    /// assert!((m.median() - 2f64.ln() / 2.0).abs() < 1e-15);
+    /// assert_eq!(m.quantile(0.0), 0.0);
+    /// assert_eq!(m.quantile(1.0), f64::INFINITY);
+    /// ```
+    fn quantile(&self, p: Self::R) -> Self::R {
+        let (zero, one) = (Self::R::zero(), Self::R::one());
+        let support = self.support();
+        if !(zero..=one).contains(&p) {
+            return Self::R::nan();
+        } else if p.is_zero() {
+            return support.lower;
+        } else if p == one {
+            return support.upper;
+        }
+
+        // Find `lower < upper` with `cdf(lower) < p <= cdf(upper)`, growing
+        // outwards from the mean where the support is unbounded.
+        let mean = self.mean();
+        let guess = if mean.is_finite() { mean } else { zero };
+        let (mut lower, mut upper) = (support.lower, support.upper);
+        let mut step = one;
+        while lower.is_infinite() {
+            let x = guess - step;
+            if x.is_infinite() {
+                return x;
+            } else if self.cdf(x) < p {
+                lower = x;
+            } else {
+                upper = upper.min(x);
+            }
+            step += step;
+        }
+        if self.cdf(lower) >= p {
+            return lower;
+        }
+        step = one;
+        while upper.is_infinite() {
+            let x = lower.max(guess) + step;
+            if x.is_infinite() {
+                return x;
+            } else if self.cdf(x) >= p {
+                upper = x;
+            } else {
+                lower = x;
+            }
+            step += step;
+        }
+
+        loop {
+            let middle = lower + (upper - lower) / (one + one);
+            if middle <= lower || middle >= upper {
+                return upper;
+            } else if self.cdf(middle) >= p {
+                upper = middle;
+            } else {
+                lower = middle;
+            }
+        }
+    }
+
+    /// The mean of the distribution, or NaN if it does not exist.
+    fn mean(&self) -> Self::R;
+
+    /// The variance of the distribution, which may be infinite, or NaN if it
+    /// does not exist.
+    fn variance(&self) -> Self::R;
+
+    /// The median of the distribution.
+    fn median(&self) -> Self::R {
+        self.quantile((Self::R::one() + Self::R::one()).recip())
+    }
+
+    /// A mode of the distribution, i.e., a point at which its density is
+    /// greatest.
+    fn mode(&self) -> Self::R;
+
+    /// The smallest closed interval outside of which the distribution has no
+    /// mass.
+    fn support(&self) -> Interval<Self::R>;
+}
+
+/// Describes a probability distribution over real numbers which has a density,
+/// and so assigns no mass to individual points.
+pub trait ContinuousDistribution: RealDistribution {
+    /// The probability density function.
+    fn density(&self, x: Self::R) -> Self::R;
+
+    /// The probability of the given subset.
+    ///
+    /// This is NaN if the subset is neither empty nor full and cannot be
+    /// decomposed into intervals.
+    fn probability(&self, domain: &(dyn RealSubset<Self::R> + '_)) -> Self::R {
+        continuous_probability(self, domain)
+    }
+}
+
+/// Computes the probability of a subset under a distribution without point
+/// masses, by summing over the intervals making up the subset.
+pub(crate) fn continuous_probability<D: RealDistribution + ?Sized>(
+    distribution: &D,
+    domain: &(dyn RealSubset<D::R> + '_),
+) -> D::R {
+    let half = (D::R::one() + D::R::one()).recip();
+    match domain.intervals() {
+        Some(intervals) => intervals.fold(D::R::zero(), |sum, i| {
+            // Subtract whichever tail probabilities are smaller, to avoid cancellation.
+            let below = distribution.cdf(i.lower);
+            sum + if below > half {
+                distribution.survival(i.lower) - distribution.survival(i.upper)
+            } else {
+                distribution.cdf(i.upper) - below
+            }
+        }),
+        None if domain.is_empty() => D::R::zero(),
+        None if domain.is_full() => D::R::one(),
+        None => D::R::nan(),
+    }
 }
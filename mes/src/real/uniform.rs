@@ -0,0 +1,96 @@
+//! Implementation of (univariate) uniform distributions.
+
+use super::{
+    interval::Interval, weighted::Weighted, ContinuousDistribution, Real, RealDistribution,
+};
+
+/// A (univariate) weighted uniform measure.
+pub type Uniform<R> = Weighted<PUniform<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) uniform distribution on a closed interval.
+///
+/// ```
+/// use mes::real::{uniform::PUniform, RealDistribution};
+///
+/// let d = PUniform {
+///     lower: 1.0f64,
+///     upper: 5.0,
+/// };
+/// assert_eq!(d.cdf(2.0), 0.25);
+/// assert_eq!(d.quantile(0.75), 4.0);
+/// assert_eq!(d.mean(), 3.0);
+/// ```
+pub struct PUniform<R: Real> {
+    /// The lower end of the interval, which must be less than the upper end.
+    pub lower: R,
+
+    /// The upper end of the interval.
+    pub upper: R,
+}
+
+impl<R: Real> RealDistribution for PUniform<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        if x <= self.lower {
+            R::zero()
+        } else if x >= self.upper {
+            R::one()
+        } else {
+            (x - self.lower) / (self.upper - self.lower)
+        }
+    }
+
+    fn survival(&self, x: R) -> R {
+        if x <= self.lower {
+            R::one()
+        } else if x >= self.upper {
+            R::zero()
+        } else {
+            (self.upper - x) / (self.upper - self.lower)
+        }
+    }
+
+    fn quantile(&self, p: R) -> R {
+        if !(R::zero()..=R::one()).contains(&p) {
+            R::nan()
+        } else if p == R::one() {
+            self.upper
+        } else {
+            self.lower + p * (self.upper - self.lower)
+        }
+    }
+
+    fn mean(&self) -> R {
+        (self.lower + self.upper) / (R::one() + R::one())
+    }
+
+    fn variance(&self) -> R {
+        let width = self.upper - self.lower;
+        width * width / R::from(12).unwrap()
+    }
+
+    fn median(&self) -> R {
+        self.mean()
+    }
+
+    /// Every point of the support is a mode, so this is the midpoint.
+    fn mode(&self) -> R {
+        self.mean()
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::closed(self.lower, self.upper)
+    }
+}
+
+impl<R: Real> ContinuousDistribution for PUniform<R> {
+    fn density(&self, x: R) -> R {
+        if self.lower <= x && x <= self.upper {
+            (self.upper - self.lower).recip()
+        } else {
+            R::zero()
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Implementation of (univariate) Weibull distributions.
+
+#![cfg(any(feature = "libm", feature = "std"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "libm", feature = "std"))))]
+
+use num_traits::{Float, FloatConst};
+
+use crate::special::ln_gamma;
+
+use super::{
+    interval::Interval, weighted::Weighted, ContinuousDistribution, Real, RealDistribution,
+};
+
+/// A (univariate) weighted Weibull measure.
+pub type Weibull<R> = Weighted<PWeibull<R>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) Weibull distribution.
+///
+/// With shape 1, this is an exponential distribution:
+/// ```
+/// use mes::real::{weibull::PWeibull, RealDistribution};
+///
+/// let d = PWeibull {
+///     shape: 1.0f64,
+///     scale: 2.0,
+/// };
+/// assert_eq!(d.survival(2.0), (-1f64).exp());
+/// assert!((d.mean() - 2.0).abs() < 1e-14);
+/// assert!((d.variance() - 4.0).abs() < 1e-14);
+/// ```
+pub struct PWeibull<R: Real> {
+    /// The shape of the distribution, which must be positive.
+    pub shape: R,
+
+    /// The scale of the distribution, which must be positive.
+    pub scale: R,
+}
+
+impl<R: Real + Float + FloatConst> PWeibull<R> {
+    /// Computes `(x / scale)^shape`, the cumulative hazard at `x`.
+    fn hazard(&self, x: R) -> R {
+        (x / self.scale).powf(self.shape)
+    }
+
+    /// Computes `Γ(1 + n / shape)`, the `n`th moment divided by `scale^n`.
+    fn moment(&self, n: R) -> R {
+        ln_gamma(R::one() + n / self.shape).exp()
+    }
+}
+
+impl<R: Real + Float + FloatConst> RealDistribution for PWeibull<R> {
+    type R = R;
+
+    fn cdf(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::zero()
+        } else {
+            -(-self.hazard(x)).exp_m1()
+        }
+    }
+
+    fn survival(&self, x: R) -> R {
+        if x <= R::zero() {
+            R::one()
+        } else {
+            (-self.hazard(x)).exp()
+        }
+    }
+
+    fn quantile(&self, p: R) -> R {
+        if !(R::zero()..=R::one()).contains(&p) {
+            <R as Float>::nan()
+        } else {
+            self.scale * (-(-p).ln_1p()).powf(Float::recip(self.shape))
+        }
+    }
+
+    fn mean(&self) -> R {
+        self.scale * self.moment(R::one())
+    }
+
+    fn variance(&self) -> R {
+        let mean = self.moment(R::one());
+        self.scale * self.scale * (self.moment(R::one() + R::one()) - mean * mean)
+    }
+
+    fn median(&self) -> R {
+        self.scale * R::LN_2().powf(Float::recip(self.shape))
+    }
+
+    fn mode(&self) -> R {
+        if self.shape > R::one() {
+            self.scale * ((self.shape - R::one()) / self.shape).powf(Float::recip(self.shape))
+        } else {
+            R::zero()
+        }
+    }
+
+    fn support(&self) -> Interval<R> {
+        Interval::closed(R::zero(), <R as Float>::infinity())
+    }
+}
+
+impl<R: Real + Float + FloatConst> ContinuousDistribution for PWeibull<R> {
+    fn density(&self, x: R) -> R {
+        if x < R::zero() {
+            R::zero()
+        } else if x.is_zero() && self.shape < R::one() {
+            <R as Float>::infinity()
+        } else {
+            let z = x / self.scale;
+            self.shape / self.scale * z.powf(self.shape - R::one()) * (-self.hazard(x)).exp()
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Weighted measures given by continuous distributions.
+
+use core::ops::{Mul, MulAssign};
+use num_traits::One;
+use with_locals::with;
+
+use crate::{Measurable, Measure, PointMeasure};
+
+use super::{ContinuousDistribution, Real, RealDistribution};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A (univariate) measure given by a continuous distribution scaled by a
+/// weight.
+///
+/// The weighted variants of the continuous distributions in [`real`](super)
+/// are aliases of this type, such as [`Uniform`]:
+/// ```
+/// use mes::{
+///     real::{
+///         interval::Interval,
+///         uniform::{PUniform, Uniform},
+///     },
+///     Measure, PointMeasure,
+/// };
+///
+/// let m = Uniform {
+///     distribution: PUniform {
+///         lower: 0.0f64,
+///         upper: 4.0,
+///     },
+///     weight: 2.0,
+/// };
+///
+/// assert_eq!(m.with_measure_at(&1.0, |x| *x), 0.5);
+/// assert_eq!(m.with_measure(&Interval::open(1.0, 10.0), |x| *x), 1.5);
+/// ```
+///
+/// [`Uniform`]: super::uniform::Uniform
+pub struct Weighted<D: RealDistribution> {
+    /// The distribution of the measure.
+    pub distribution: D,
+
+    /// The weight of the measure.
+    pub weight: D::R,
+}
+
+impl<D: RealDistribution> From<D> for Weighted<D> {
+    fn from(distribution: D) -> Self {
+        Self {
+            distribution,
+            weight: D::R::one(),
+        }
+    }
+}
+
+impl<D: RealDistribution> Mul<D::R> for Weighted<D> {
+    type Output = Self;
+
+    fn mul(self, rhs: D::R) -> Self::Output {
+        Self {
+            distribution: self.distribution,
+            weight: self.weight * rhs,
+        }
+    }
+}
+
+impl<D: RealDistribution> MulAssign<D::R> for Weighted<D> {
+    fn mul_assign(&mut self, rhs: D::R) {
+        self.weight *= rhs;
+    }
+}
+
+impl<'subset, D: ContinuousDistribution + Clone> Measure<'subset> for Weighted<D> {
+    type R = D::R;
+
+    type Space = D::R;
+
+    type Measurement = D::R;
+
+    type PMeasure = D;
+
+    #[with]
+    fn measure<'a>(
+        &'a self,
+        domain: &'a <Self::Space as Measurable>::Subset<'a>,
+    ) -> &'ref Self::Measurement
+    where
+        'subset: 'a,
+    {
+        &(self.weight * self.distribution.probability(domain))
+    }
+
+    fn normalize(&self) -> Option<Self::PMeasure> {
+        D::R::normalize_static([self.weight])?;
+        Some(self.distribution.clone())
+    }
+}
+
+impl<'subset, D: ContinuousDistribution + Clone> PointMeasure<'subset> for Weighted<D> {
+    type PointMeasurement = D::R;
+
+    #[with]
+    fn measure_at(&self, value: &Self::Space) -> &'ref Self::PointMeasurement {
+        &(self.weight * self.distribution.density(*value))
+    }
+}
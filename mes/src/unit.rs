@@ -5,16 +5,31 @@ use type_variance::Contravariant;
 use with_locals::with;
 
 use crate::{
-    any, real::Real, util::iter::LocalIterator, DiracMeasure, Measurable, MeasurableFn, Measure,
+    all, any, real::Real, util::iter::LocalIterator, BitSubset, ConditionableMeasure, DiracMeasure,
+    FiniteMeasurable, FiniteMeasure, IntegrableMeasure, Measurable, MeasurableFn, Measure,
     PointMeasurable, PointMeasure, SubsetProxy,
 };
 
+#[cfg(feature = "alloc")]
+use crate::OwnedMeasurable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// A subset of the unit type.
 pub struct UnitSubset {
     /// Whether the subset is the full or empty subset.
     pub full: bool,
 }
 
+impl From<UnitSubset> for BitSubset<1> {
+    fn from(s: UnitSubset) -> Self {
+        if s.full {
+            Self::full(<()>::CARDINALITY)
+        } else {
+            Self::empty(<()>::CARDINALITY)
+        }
+    }
+}
+
 /// A function whose codomain is the unit type.
 pub struct UnitFunction<T: ?Sized>(Contravariant<T>);
 
@@ -62,6 +77,18 @@ impl Measurable for () {
         !s.full
     }
 
+    fn subset_is_full(s: &Self::Subset<'_>) -> bool {
+        s.full
+    }
+
+    fn subset_includes(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+        s.full || !t.full
+    }
+
+    fn subset_eq(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> bool {
+        s.full == t.full
+    }
+
     #[with]
     fn subset_complement(s: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
         &UnitSubset { full: !s.full }
@@ -69,7 +96,7 @@ impl Measurable for () {
 
     #[with]
     fn subset_union<'a>(
-        mut subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
+        subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
     ) -> &'ref Self::Subset<'ref>
     where
         Self: 'a,
@@ -88,6 +115,35 @@ impl Measurable for () {
         // }
         // &UnitSubset { full }
     }
+
+    #[with]
+    fn subset_intersect<'a>(
+        subsets: impl LocalIterator<Item = SubsetProxy<'a, Self>> + Clone + 'a,
+    ) -> &'ref Self::Subset<'ref>
+    where
+        Self: 'a,
+    {
+        &UnitSubset {
+            full: all!(proxy in subsets => proxy.with_access(|s| s.full)),
+        }
+    }
+
+    #[with]
+    fn subset_difference(s: &Self::Subset<'_>, t: &Self::Subset<'_>) -> &'ref Self::Subset<'ref> {
+        &UnitSubset {
+            full: s.full && !t.full,
+        }
+    }
+
+    #[with]
+    fn subset_symmetric_difference(
+        s: &Self::Subset<'_>,
+        t: &Self::Subset<'_>,
+    ) -> &'ref Self::Subset<'ref> {
+        &UnitSubset {
+            full: s.full != t.full,
+        }
+    }
 }
 
 impl PointMeasurable for () {
@@ -99,6 +155,33 @@ impl PointMeasurable for () {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl OwnedMeasurable for () {
+    type Owned = UnitSubset;
+
+    fn subset_to_owned(s: &Self::Subset<'_>) -> Option<Self::Owned> {
+        Some(*s)
+    }
+
+    #[with]
+    fn subset_from_owned(s: &Self::Owned) -> &'ref Self::Subset<'ref> {
+        s
+    }
+}
+
+impl FiniteMeasurable for () {
+    const CARDINALITY: usize = 1;
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        (index == 0).then_some(())
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, PartialOrd, Add, AddAssign, Mul, MulAssign)]
 /// A measure on the unit type.
 pub struct UnitMeasure<R: Real> {
@@ -156,3 +239,25 @@ impl<'subset, R: Real> DiracMeasure<'subset> for UnitMeasure<R> {
         Self { weight: R::one() }
     }
 }
+
+impl<'subset, R: Real> FiniteMeasure<'subset> for UnitMeasure<R> {
+    fn from_point_masses(mut mass: impl FnMut(&Self::Space) -> Self::R) -> Self {
+        Self { weight: mass(&()) }
+    }
+}
+
+impl<'subset, R: Real> ConditionableMeasure<'subset> for UnitMeasure<R> {
+    fn condition(&self, event: &UnitSubset) -> Option<Self::PMeasure> {
+        if event.full {
+            self.normalize()
+        } else {
+            None
+        }
+    }
+}
+
+impl<'subset, R: Real> IntegrableMeasure<'subset> for UnitMeasure<R> {
+    fn integrate(&self, mut f: impl FnMut(&Self::Space) -> Self::R) -> Self::R {
+        self.weight * f(&())
+    }
+}
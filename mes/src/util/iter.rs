@@ -1,9 +1,4 @@
-use core::{
-    marker::PhantomData,
-    mem::{transmute, MaybeUninit},
-};
-
-use with_locals::with;
+use core::{marker::PhantomData, mem::MaybeUninit};
 
 use super::{BasicLGType, LGType};
 